@@ -1,17 +1,86 @@
-use crate::config::AppConfig;
+use crate::config::{AppConfig, DebouncedSave, FetchMode, IdleDisplay};
+use crate::diagnostics;
 use crate::error::LyricsifyError;
-use crate::lyrics_fetcher::LyricsFetcher;
-use crate::spotify_client::{SpotifyClient, TrackInfo};
-use crate::ui_manager::{MenuBar, UIManager};
+use crate::last_track::LastTrack;
+use crate::lyrics_fetcher::{self, LyricsFetcher, LyricsResult};
+use crate::spotify_client::{DeviceInfo, PlaybackProgress, SpotifyClient, TrackInfo};
+use crate::log_level;
+use crate::log_privacy::{self, logged};
+use crate::log_tail;
+use crate::metrics::{self, MetricsInput, MetricsServer};
+use crate::settings_transfer;
+use crate::shutdown::{self, FlushStep, ShutdownFlag};
+use crate::ui_manager::{
+    app_or_player_frontmost, choose_open_path, choose_save_path, copy_to_clipboard,
+    prompt_for_text, show_notification, MenuBar, UIManager,
+};
+use crate::widget_export::WidgetState;
+use objc2_foundation::MainThreadMarker;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::signal::unix::{signal, SignalKind};
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::{interval, Duration, MissedTickBehavior};
+
+/// Minimum time between now playing notifications, so rapid skips don't
+/// produce a burst of banners
+const NOTIFICATION_MIN_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How long after a track's predicted end to poll for the next one, giving
+/// Spotify a moment to switch over
+const TRACK_END_POLL_DELAY: Duration = Duration::from_millis(750);
+
+/// Tracks closer to their end than this keep the end-of-track poll already
+/// scheduled, so a position stuck at the end can't cause a burst of polls
+const MIN_TRACK_END_LEAD: Duration = Duration::from_secs(2);
+
+/// Time between background fetches when warming the cache for a playlist
+const CACHE_WARMING_INTERVAL: Duration = Duration::from_secs(3);
 
 /// Events that can occur in the application
 #[derive(Debug, Clone)]
 pub enum AppEvent {
     TrackChanged(TrackInfo),
+    PlaybackStopped,
+    /// Spotify is playing an ad instead of a track
+    AdvertisementPlaying,
+    PlaybackProgress(PlaybackProgress),
     LyricsRetrieved(Option<String>),
+    /// The first track in the Spotify queue, `None` when the queue is empty
+    UpNext(Option<TrackInfo>),
     ToggleOverlay,
+    HideOverlay,
+    /// Step the overlay opacity by this amount
+    AdjustOpacity(f64),
+    ToggleNotifications,
+    /// Fetch lyrics for the playing track in on-demand mode
+    FetchLyricsNow,
+    SkipCurrentTrack,
+    ClearSkippedTracks,
+    ManualLookup,
+    EndManualLookup,
+    RunDiagnostics,
+    CopyDiagnostics,
+    ExportSettings,
+    ImportSettings,
+    /// Step the log level: Info, Debug, Trace, then back to Info
+    CycleLogLevel,
+    RefreshToken,
+    /// rspotify refreshed the token in memory while polling
+    TokenRefreshed,
+    /// Restart the playback poll loop from the menu
+    RestartPolling,
+    /// The poll loop crashed too often in a row and was not restarted
+    PollingStopped,
     Authenticate,
+    /// A device was picked from the device menu, by index in the last list
+    SelectDevice(usize),
+    RefreshDevices,
+    SystemWoke,
+    /// Another app came to the front; true when it's Lyricsify or Spotify
+    FocusChanged(bool),
     Quit,
     SpotifyError(String),
 }
@@ -19,50 +88,109 @@ pub enum AppEvent {
 /// Main application structure coordinating all components
 pub struct App {
     spotify_client: SpotifyClient,
-    lyrics_fetcher: LyricsFetcher,
+    /// Shared with background lyrics fetches
+    lyrics_fetcher: Arc<LyricsFetcher>,
     ui_manager: UIManager,
     menu_bar: MenuBar,
     config: AppConfig,
+    /// Pending save of `config` from a menu toggle
+    config_save: DebouncedSave,
     event_rx: mpsc::Receiver<AppEvent>,
     event_tx: mpsc::Sender<AppEvent>,
+    /// Track currently playing, as of the last track change
+    current_track: Option<TrackInfo>,
+    /// Whether the overlay is in its idle (nothing playing) state
+    idle: bool,
+    /// Last reported playback position and when it was received
+    playback: Option<(PlaybackProgress, Instant)>,
+    /// When to poll for the next track, just after the playing one should end
+    track_end_poll: Option<tokio::time::Instant>,
+    /// When the last now playing notification was shown
+    last_notification: Option<Instant>,
+    /// Playlist tracks still to be fetched into the cache
+    warming_queue: VecDeque<TrackInfo>,
+    /// URI of the playlist the warming queue was filled from
+    warmed_playlist: Option<String>,
+    /// Background fetch for the track most recently taken off the warming queue
+    warming_task: Option<JoinHandle<()>>,
+    /// Local Prometheus endpoint, when enabled
+    metrics_server: Option<MetricsServer>,
+    /// Spotify Connect devices as last listed, in device menu order
+    devices: Vec<DeviceInfo>,
+    /// Whether the overlay shows a manual lookup instead of the playing track
+    viewing_lookup: bool,
+    /// Whether the playing track's lyrics are waiting for the overlay to be shown
+    fetch_deferred: bool,
+    /// Tells the poller and menu forwarder to stop sending events
+    shutting_down: ShutdownFlag,
+    mtm: MainThreadMarker,
 }
 
 impl App {
     /// Create a new App instance, initializing all components
-    pub fn new() -> Result<Self, LyricsifyError> {
+    ///
+    /// Must be called on the main thread (proven by `mtm`) since it creates
+    /// the AppKit overlay window and status item.
+    pub fn new(mtm: MainThreadMarker) -> Result<Self, LyricsifyError> {
         log::info!("Initializing application components");
 
         // Load configuration from disk
-        let config = AppConfig::load()?;
+        let mut config = AppConfig::load()?;
         log::info!("Configuration loaded");
+        log_privacy::set_redacted(config.redact_track_info);
+
+        // Launch into the menu bar only; showing the overlay from the menu
+        // works (and is saved) as usual
+        if config.start_hidden {
+            log::info!("Starting with the overlay hidden");
+            config.overlay_visible = false;
+        }
 
         // Create event channel for communication between components
         let (event_tx, event_rx) = mpsc::channel(100);
 
-        // Create unbounded channel for menu bar (UI events need to be non-blocking)
+        // Create unbounded channel for menu bar and overlay events (must be non-blocking)
         let (menu_event_tx, mut menu_event_rx) = mpsc::unbounded_channel();
 
         // Initialize Spotify client
-        let spotify_client = SpotifyClient::new()?;
+        let spotify_client = SpotifyClient::new(&config)?;
         log::info!("Spotify client initialized");
 
         // Initialize lyrics fetcher
-        let lyrics_fetcher = LyricsFetcher::new()?;
+        let mut lyrics_fetcher = LyricsFetcher::new(&config);
+        if config.strip_section_headers {
+            lyrics_fetcher.add_transform(Box::new(lyrics_fetcher::strip_section_headers));
+        }
         log::info!("Lyrics fetcher initialized");
+        if !lyrics_fetcher.is_available() {
+            // Tracks are still shown, just without lyrics
+            show_notification(
+                "Lyricsify",
+                "Lyrics are unavailable: network access couldn't be set up. See the log.",
+            );
+        }
 
         // Initialize UI manager with overlay window
-        let ui_manager = UIManager::new(config.clone())?;
+        let ui_manager = UIManager::new(config.clone(), menu_event_tx.clone(), mtm)?;
         log::info!("UI manager initialized");
 
         // Initialize menu bar
-        let menu_bar = MenuBar::new(menu_event_tx)?;
+        let menu_bar = MenuBar::new(
+            menu_event_tx,
+            &config.status_symbol_name,
+            config.menu_style,
+            mtm,
+        )?;
         log::info!("Menu bar initialized");
 
-        // Spawn a task to forward menu events to the main event channel
+        // Spawn a task to forward menu events to the main event channel,
+        // stopping once shutdown begins rather than sending into a closing channel
+        let shutting_down = ShutdownFlag::new();
         let event_tx_clone = event_tx.clone();
+        let forwarder_shutdown = shutting_down.clone();
         tokio::spawn(async move {
             while let Some(event) = menu_event_rx.recv().await {
-                if event_tx_clone.send(event).await.is_err() {
+                if forwarder_shutdown.is_triggered() || event_tx_clone.send(event).await.is_err() {
                     break;
                 }
             }
@@ -70,12 +198,27 @@ impl App {
 
         Ok(Self {
             spotify_client,
-            lyrics_fetcher,
+            lyrics_fetcher: Arc::new(lyrics_fetcher),
             ui_manager,
             menu_bar,
             config,
+            config_save: DebouncedSave::default(),
             event_rx,
             event_tx,
+            current_track: None,
+            idle: false,
+            playback: None,
+            track_end_poll: None,
+            last_notification: None,
+            warming_queue: VecDeque::new(),
+            warmed_playlist: None,
+            warming_task: None,
+            metrics_server: None,
+            devices: Vec::new(),
+            viewing_lookup: false,
+            fetch_deferred: false,
+            shutting_down,
+            mtm,
         })
     }
 
@@ -83,6 +226,30 @@ impl App {
     pub async fn initialize(&mut self) -> Result<(), LyricsifyError> {
         log::info!("Initializing application");
 
+        // Show the last known track right away instead of a blank overlay
+        // while the first poll runs, and seed the cache in case it's still playing
+        if let Some(last_track) = LastTrack::load() {
+            log::info!("Showing last known track until the first poll completes");
+            if let Some(overlay) = self.ui_manager.overlay_window() {
+                overlay.update_lyrics(&last_track.stale_display_text())?;
+            }
+            if let Some(lyrics) = last_track.lyrics {
+                self.lyrics_fetcher.insert_cached(&last_track.track_id, lyrics);
+            }
+        }
+
+        if let Some(port) = self.config.metrics_port {
+            match MetricsServer::start(port).await {
+                Ok(server) => self.metrics_server = Some(server),
+                Err(e) => log::warn!("Failed to start metrics server on port {}: {}", port, e),
+            }
+        }
+
+        if self.config.startup_delay_secs > 0 {
+            self.wait_for_startup_delay(Duration::from_secs(self.config.startup_delay_secs))
+                .await?;
+        }
+
         // Initialize Spotify client (load token from keychain)
         let authenticated = self.spotify_client.initialize().await?;
 
@@ -92,18 +259,59 @@ impl App {
         if authenticated {
             log::info!("Authenticated with Spotify, starting track polling");
             // Start polling for track changes
-            self.spotify_client.start_polling(self.event_tx.clone());
+            self.spotify_client
+                .start_polling(self.event_tx.clone(), self.shutting_down.clone());
+            // Free accounts can't switch devices, so check before listing them
+            self.spotify_client.refresh_account_product().await;
+            self.refresh_devices().await;
         } else {
             log::warn!("Not authenticated with Spotify. Please authenticate from the menu bar.");
             // Display "Not authenticated" message in overlay
             if let Some(overlay) = self.ui_manager.overlay_window() {
                 overlay.update_lyrics("Not authenticated")?;
             }
+
+            if self.config.auto_authenticate && self.spotify_client.has_credentials() {
+                log::info!("Credentials present but no token, starting sign-in automatically");
+                self.start_authentication(self.config.auto_authenticate_opens_browser)?;
+            }
+        }
+
+        // The status item didn't exist yet when the overlay was first shown
+        if self.overlay_visible() {
+            self.anchor_overlay();
         }
 
-        // Update menu bar visibility state based on config
+        // Start dimmed if the app launched behind something else
+        self.handle_focus_changed(app_or_player_frontmost());
+
+        // Match the menu to the window itself so the two can't disagree
+        self.menu_bar.update_visibility_state(self.overlay_visible())?;
         self.menu_bar
-            .update_visibility_state(self.config.overlay_visible)?;
+            .update_notifications_state(self.config.now_playing_notifications)?;
+        self.menu_bar.update_skip_state(false, self.config.skipped_tracks.len())?;
+
+        Ok(())
+    }
+
+    /// Wait out the configured startup delay before touching the network
+    ///
+    /// The main loop isn't running yet, so AppKit events are pumped here to
+    /// keep the overlay and menu responsive while "Starting…" is shown.
+    async fn wait_for_startup_delay(&mut self, delay: Duration) -> Result<(), LyricsifyError> {
+        log::info!("Waiting {}s before connecting to Spotify", delay.as_secs());
+
+        if let Some(overlay) = self.ui_manager.overlay_window() {
+            overlay.update_lyrics("Starting…")?;
+        }
+
+        let deadline = tokio::time::Instant::now() + delay;
+        let mut ui_tick = interval(Duration::from_millis(16));
+        ui_tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        while tokio::time::Instant::now() < deadline {
+            ui_tick.tick().await;
+            self.ui_manager.pump_events();
+        }
 
         Ok(())
     }
@@ -112,25 +320,125 @@ impl App {
     pub async fn run(&mut self) -> Result<(), LyricsifyError> {
         log::info!("Starting main event loop");
 
+        // Spotify only reports progress on each poll, so advance the
+        // progress display locally between polls
+        let mut progress_tick = interval(Duration::from_secs(1));
+
+        // AppKit events are serviced from this loop rather than NSApplication::run
+        let mut ui_tick = interval(Duration::from_millis(16));
+        ui_tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        // Playlist warming fetches one track per tick to stay well within rate limits
+        let mut warming_tick = interval(CACHE_WARMING_INTERVAL);
+        warming_tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        // Quit cleanly on Ctrl-C or `kill`, so nothing is lost on the way out
+        let mut interrupt = signal(SignalKind::interrupt())?;
+        let mut terminate = signal(SignalKind::terminate())?;
+        // `kill -USR1` cycles the log level, for debugging without the menu
+        let mut user_signal = signal(SignalKind::user_defined1())?;
+
         loop {
             tokio::select! {
-                Some(event) = self.event_rx.recv() => {
+                event = self.event_rx.recv() => {
+                    let Some(event) = event else {
+                        log::warn!("Event channel closed, exiting");
+                        break;
+                    };
+
                     match event {
                         AppEvent::TrackChanged(track) => {
                             self.handle_track_changed(track).await?;
                         }
+                        AppEvent::PlaybackStopped => {
+                            self.handle_playback_stopped().await?;
+                        }
+                        AppEvent::AdvertisementPlaying => {
+                            self.handle_advertisement_playing()?;
+                        }
+                        AppEvent::PlaybackProgress(progress) => {
+                            self.handle_playback_progress(progress)?;
+                        }
                         AppEvent::LyricsRetrieved(lyrics) => {
                             self.handle_lyrics_retrieved(lyrics)?;
                         }
+                        AppEvent::UpNext(track) => {
+                            self.handle_up_next(track)?;
+                        }
                         AppEvent::ToggleOverlay => {
-                            self.handle_toggle_overlay()?;
+                            self.handle_toggle_overlay().await?;
+                        }
+                        AppEvent::HideOverlay => {
+                            self.handle_hide_overlay().await?;
+                        }
+                        AppEvent::AdjustOpacity(delta) => {
+                            self.handle_adjust_opacity(delta);
+                        }
+                        AppEvent::ToggleNotifications => {
+                            self.handle_toggle_notifications()?;
+                        }
+                        AppEvent::FetchLyricsNow => {
+                            self.handle_fetch_lyrics_now().await?;
+                        }
+                        AppEvent::SkipCurrentTrack => {
+                            self.handle_skip_current_track()?;
+                        }
+                        AppEvent::ClearSkippedTracks => {
+                            self.handle_clear_skipped_tracks()?;
+                        }
+                        AppEvent::ManualLookup => {
+                            self.handle_manual_lookup().await?;
+                        }
+                        AppEvent::EndManualLookup => {
+                            self.handle_end_manual_lookup().await?;
+                        }
+                        AppEvent::RunDiagnostics => {
+                            self.handle_run_diagnostics().await?;
+                        }
+                        AppEvent::CopyDiagnostics => {
+                            self.handle_copy_diagnostics().await;
+                        }
+                        AppEvent::ExportSettings => {
+                            self.handle_export_settings();
+                        }
+                        AppEvent::ImportSettings => {
+                            self.handle_import_settings()?;
+                        }
+                        AppEvent::CycleLogLevel => {
+                            self.handle_cycle_log_level();
+                        }
+                        AppEvent::RefreshToken => {
+                            self.handle_refresh_token().await?;
+                        }
+                        AppEvent::TokenRefreshed => {
+                            if let Err(e) = self.spotify_client.save_token_if_refreshed().await {
+                                log::warn!("Failed to save refreshed token: {}", e);
+                            }
+                        }
+                        AppEvent::RestartPolling => {
+                            self.handle_restart_polling().await?;
+                        }
+                        AppEvent::PollingStopped => {
+                            self.handle_polling_stopped()?;
                         }
                         AppEvent::Authenticate => {
                             self.handle_authenticate().await?;
                         }
+                        AppEvent::SelectDevice(index) => {
+                            self.handle_select_device(index).await?;
+                        }
+                        AppEvent::RefreshDevices => {
+                            self.refresh_devices().await;
+                        }
+                        AppEvent::SystemWoke => {
+                            self.handle_system_woke().await?;
+                        }
+                        AppEvent::FocusChanged(focused) => {
+                            self.handle_focus_changed(focused);
+                        }
                         AppEvent::Quit => {
                             log::info!("Quit event received");
-                            self.shutdown()?;
+                            self.shutdown();
                             break;
                         }
                         AppEvent::SpotifyError(error) => {
@@ -138,10 +446,46 @@ impl App {
                         }
                     }
                 }
-                else => {
-                    log::warn!("Event channel closed, exiting");
+                _ = interrupt.recv() => {
+                    log::info!("Interrupted, quitting");
+                    self.shutdown();
                     break;
                 }
+                _ = terminate.recv() => {
+                    log::info!("Terminated, quitting");
+                    self.shutdown();
+                    break;
+                }
+                _ = user_signal.recv() => {
+                    self.handle_cycle_log_level();
+                }
+                _ = progress_tick.tick() => {
+                    self.update_progress_display()?;
+                    self.publish_metrics();
+                    if let Some(overlay) = self.ui_manager.overlay_window() {
+                        overlay.save_config_if_due();
+                    }
+                    if self.config_save.take_due(Instant::now()) {
+                        self.sync_overlay_position();
+                        match self.config.save() {
+                            Ok(()) | Err(LyricsifyError::ConfigDirUnwritable) => {}
+                            Err(e) => log::warn!("Failed to save configuration: {}", e),
+                        }
+                    }
+                }
+                _ = ui_tick.tick() => {
+                    self.ui_manager.pump_events();
+                }
+                _ = tokio::time::sleep_until(
+                    self.track_end_poll.unwrap_or_else(tokio::time::Instant::now)
+                ), if self.track_end_poll.is_some() => {
+                    log::debug!("Track should have ended, polling for the next one");
+                    self.track_end_poll = None;
+                    self.spotify_client.poll_now();
+                }
+                _ = warming_tick.tick(), if !self.warming_queue.is_empty() => {
+                    self.warm_next_track();
+                }
             }
         }
 
@@ -152,25 +496,165 @@ impl App {
     async fn handle_track_changed(&mut self, track: TrackInfo) -> Result<(), LyricsifyError> {
         log::info!(
             "Handling track change: {} by {}",
-            track.name,
-            track.artists.join(", ")
+            logged(&track.name),
+            logged(&track.artists.join(", "))
         );
 
+        // Progress from the previous track must not be compared against this one
+        self.playback = None;
+        self.track_end_poll = None;
+        self.fetch_deferred = false;
+
+        // Live playback takes over from a lookup unless it's pinned
+        if self.viewing_lookup && !self.config.pin_manual_lookup {
+            self.viewing_lookup = false;
+            self.menu_bar.update_lookup_state(false)?;
+        }
+
+        if self.idle {
+            self.leave_idle_display()?;
+        }
+
+        // Announce the new track to VoiceOver users
+        let now_playing = format!("{} by {}", track.name, track.artists.join(", "));
+        if let Some(overlay) = self.ui_manager.overlay_window() {
+            overlay.set_now_playing(Some(&now_playing))?;
+        }
+        self.menu_bar.update_now_playing(Some(&now_playing))?;
+
+        self.current_track = Some(track.clone());
+        let skipped = self.is_skipped(&track.id);
+        self.menu_bar.update_skip_state(!skipped, self.config.skipped_tracks.len())?;
+        self.menu_bar.update_fetch_now_state(false)?;
+
+        let fetched = self.show_track_lyrics(track, skipped, &now_playing).await?;
+
+        // Only once the lyrics are up, so the queue lookup never delays them.
+        // Up next is shown even when this track's lyrics are skipped or not
+        // fetched yet.
+        let prefetch = fetched && self.config.prefetch_next_track;
+        self.look_ahead(self.config.show_up_next, prefetch).await;
+        Ok(())
+    }
+
+    /// Fetch and show the new track's lyrics, or why they aren't shown
+    ///
+    /// Returns whether the lyrics were fetched.
+    async fn show_track_lyrics(
+        &mut self,
+        track: TrackInfo,
+        skipped: bool,
+        now_playing: &str,
+    ) -> Result<bool, LyricsifyError> {
+        // Tracks on the skip list never hit the network
+        if skipped {
+            log::info!("Lyrics skipped for {} by user request", logged(&track.name));
+            if !self.viewing_lookup {
+                self.update_overlay_text("No lyrics (skipped)")?;
+            }
+            return Ok(false);
+        }
+
         // Check if authenticated
         if !self.spotify_client.is_authenticated().await {
             log::warn!("Not authenticated, cannot fetch lyrics");
             if let Some(overlay) = self.ui_manager.overlay_window() {
                 overlay.update_lyrics("Not authenticated")?;
             }
+            return Ok(false);
+        }
+
+        // Only the header until the user asks; cached lyrics cost nothing to show
+        if self.awaiting_fetch_request(&track.id) {
+            log::info!("On-demand mode, not fetching lyrics for {}", logged(&track.name));
+            self.menu_bar.update_fetch_now_state(true)?;
+            if !self.viewing_lookup {
+                self.update_overlay_text(now_playing)?;
+            }
+            return Ok(false);
+        }
+
+        // Nobody can see the lyrics, so fetch them once the overlay is shown
+        if self.config.defer_fetch_while_hidden && !self.overlay_visible() {
+            log::info!("Overlay hidden, deferring lyrics fetch for {}", logged(&track.name));
+            self.fetch_deferred = true;
+            if !self.viewing_lookup {
+                self.update_overlay_text(now_playing)?;
+            }
+            return Ok(false);
+        }
+
+        self.fetch_track_lyrics(track).await?;
+        Ok(true)
+    }
+
+    /// Whether lyrics for this track should wait for "Fetch Lyrics Now"
+    fn awaiting_fetch_request(&self, track_id: &str) -> bool {
+        self.config.fetch_mode == FetchMode::OnDemand && !self.lyrics_fetcher.is_cached(track_id)
+    }
+
+    /// Fetch lyrics for the playing track when asked to in on-demand mode
+    async fn handle_fetch_lyrics_now(&mut self) -> Result<(), LyricsifyError> {
+        let Some(track) = self.current_track.clone() else {
+            log::debug!("Nothing playing, no lyrics to fetch");
+            return Ok(());
+        };
+        if self.is_skipped(&track.id) {
             return Ok(());
         }
+        log::info!("Fetching lyrics for {} on request", logged(&track.name));
 
-        // Fetch lyrics for the new track
+        // The lyrics replace whatever lookup is showing, as they were asked for
+        if self.viewing_lookup {
+            self.viewing_lookup = false;
+            self.menu_bar.update_lookup_state(false)?;
+        }
+        self.fetch_track_lyrics(track).await?;
+        self.look_ahead(false, self.config.prefetch_next_track).await;
+        Ok(())
+    }
+
+    /// Fetch and show lyrics for the playing track, then queue playlist warming
+    async fn fetch_track_lyrics(&mut self, track: TrackInfo) -> Result<(), LyricsifyError> {
+        self.menu_bar.update_fetch_now_state(false)?;
+
+        // Cache hits are instant, so only show the loading text for real fetches
+        if !self.viewing_lookup
+            && !self.config.loading_template.is_empty()
+            && !self.lyrics_fetcher.is_cached(&track.id)
+        {
+            let text = track.fallback_text(&self.config.loading_template);
+            if let Some(overlay) = self.ui_manager.overlay_window() {
+                overlay.update_lyrics(&text)?;
+                overlay.display_now();
+            }
+        }
+
+        // Fetch lyrics for the new track; the overlay doesn't follow synced
+        // lyrics yet, so they're shown as plain text
         let artist = track.artists.first().unwrap_or(&String::new()).clone();
         let lyrics = self
             .lyrics_fetcher
-            .fetch_lyrics(&track.id, &artist, &track.name)
-            .await?;
+            .fetch_lyrics(&track.id, &artist, &track.name, Some(track.duration_ms))
+            .await?
+            .into_text();
+
+        if self.config.now_playing_notifications {
+            self.notify_now_playing(&track, lyrics.is_some());
+        }
+
+        // Remember this track for instant display on the next launch; the
+        // lyrics are saved as cached, since that's where they're seeded back
+        let last_track = LastTrack {
+            track_id: track.id.clone(),
+            name: track.name.clone(),
+            artists: track.artists.clone(),
+            lyrics: self.lyrics_fetcher.peek_cached(&track.id),
+        };
+        match last_track.save() {
+            Ok(()) | Err(LyricsifyError::ConfigDirUnwritable) => {}
+            Err(e) => log::warn!("Failed to save last track: {}", e),
+        }
 
         // Send lyrics retrieved event
         self.event_tx
@@ -180,11 +664,370 @@ impl App {
                 LyricsifyError::UIError(format!("Failed to send lyrics retrieved event: {}", e))
             })?;
 
+        if self.config.warm_playlist_cache {
+            self.queue_playlist_warming().await;
+        }
+
+        Ok(())
+    }
+
+    /// Show that an ad is playing, rather than blanking the overlay as if
+    /// playback stopped
+    ///
+    /// The poller reports the next track even if it's the one from before the
+    /// ad, which brings its lyrics back.
+    fn handle_advertisement_playing(&mut self) -> Result<(), LyricsifyError> {
+        self.playback = None;
+        self.track_end_poll = None;
+        if self.idle {
+            self.leave_idle_display()?;
+        }
+
+        self.menu_bar.update_now_playing(Some("Advertisement"))?;
+        if !self.viewing_lookup {
+            self.update_overlay_text("Advertisement")?;
+        }
+        Ok(())
+    }
+
+    /// Handle playback stopping by switching the overlay to its idle display
+    async fn handle_playback_stopped(&mut self) -> Result<(), LyricsifyError> {
+        log::info!("Playback stopped");
+
+        // With no active device Spotify reports nothing playing at all, so
+        // say why rather than leaving the user with a blank overlay
+        let has_active_device = self.refresh_devices().await;
+        let message = if has_active_device {
+            "Nothing playing"
+        } else {
+            "No active Spotify device — start playing somewhere"
+        };
+
+        self.playback = None;
+        self.track_end_poll = None;
+        self.current_track = None;
+        self.fetch_deferred = false;
+        self.idle = true;
+        self.export_widget_state(WidgetState::stopped());
+        self.menu_bar.update_now_playing(None)?;
+        self.menu_bar.update_skip_state(false, self.config.skipped_tracks.len())?;
+        self.menu_bar.update_fetch_now_state(false)?;
+        self.menu_bar.update_up_next(None)?;
+
+        if let Some(overlay) = self.ui_manager.overlay_window() {
+            overlay.set_now_playing(None)?;
+            match self.config.idle_display {
+                IdleDisplay::Full => overlay.update_lyrics(message)?,
+                IdleDisplay::Collapsed => overlay.collapse(message)?,
+                IdleDisplay::Hidden => overlay.conceal()?,
+            }
+        }
         Ok(())
     }
 
+    /// Re-list Spotify Connect devices and rebuild the device menu
+    ///
+    /// Returns whether any device is active. A failed listing keeps the
+    /// previous list and counts as active, so errors don't produce a
+    /// misleading "no device" message.
+    async fn refresh_devices(&mut self) -> bool {
+        match self.spotify_client.list_devices().await {
+            Ok(devices) => {
+                self.devices = devices;
+                let can_switch = self.spotify_client.can_control_playback();
+                if let Err(e) = self.menu_bar.update_devices(&self.devices, can_switch) {
+                    log::warn!("Failed to update device menu: {}", e);
+                }
+                self.devices.iter().any(|device| device.is_active)
+            }
+            Err(e) => {
+                log::warn!("Failed to list Spotify devices: {}", e);
+                true
+            }
+        }
+    }
+
+    /// Move playback to the device picked from the device menu
+    async fn handle_select_device(&mut self, index: usize) -> Result<(), LyricsifyError> {
+        let Some(device) = self.devices.get(index).cloned() else {
+            log::warn!("Selected device {} is no longer listed", index);
+            return Ok(());
+        };
+
+        // Devices are disabled in the menu for free accounts, but a selection
+        // made before the account type was known may still be queued
+        if !self.spotify_client.can_control_playback() {
+            log::info!("Not transferring playback, the account isn't Premium");
+            show_notification("Lyricsify", "Switching devices requires Spotify Premium.");
+            return Ok(());
+        }
+
+        log::info!("Transferring playback to {}", device.name);
+        match self.spotify_client.transfer_playback(&device.id).await {
+            Ok(()) => {
+                self.spotify_client.poll_now();
+                self.refresh_devices().await;
+            }
+            Err(e) => {
+                log::warn!("Failed to transfer playback to {}: {}", device.name, e);
+                show_notification(
+                    "Lyricsify",
+                    &format!(
+                        "Couldn't play on {}. If this keeps happening, authenticate again.",
+                        device.name
+                    ),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Undo the idle display now that something is playing again
+    fn leave_idle_display(&mut self) -> Result<(), LyricsifyError> {
+        self.idle = false;
+
+        if let Some(overlay) = self.ui_manager.overlay_window() {
+            match self.config.idle_display {
+                IdleDisplay::Full => {}
+                IdleDisplay::Collapsed => overlay.expand()?,
+                // Stay hidden if the user turned the overlay off meanwhile
+                IdleDisplay::Hidden if self.config.overlay_visible => overlay.reveal()?,
+                IdleDisplay::Hidden => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Queue the tracks of the playlist being played for cache warming
+    ///
+    /// Does nothing if playback isn't from a playlist or the playlist was
+    /// already queued. At most half the cache is used, so warming doesn't
+    /// evict lyrics for tracks played from elsewhere.
+    async fn queue_playlist_warming(&mut self) {
+        let playlist_uri = match self.spotify_client.current_playlist_uri().await {
+            Ok(Some(uri)) => uri,
+            Ok(None) => return,
+            Err(e) => {
+                log::warn!("Failed to get playback context for cache warming: {}", e);
+                return;
+            }
+        };
+        if self.warmed_playlist.as_deref() == Some(playlist_uri.as_str()) {
+            return;
+        }
+
+        let limit = self.lyrics_fetcher.cache_capacity() / 2;
+        match self.spotify_client.get_context_tracks(&playlist_uri, limit).await {
+            Ok(tracks) => {
+                log::info!("Warming lyrics cache for {} playlist tracks", tracks.len());
+                self.warming_queue = tracks.into();
+                self.warmed_playlist = Some(playlist_uri);
+            }
+            Err(e) => log::warn!("Failed to get playlist tracks for cache warming: {}", e),
+        }
+    }
+
+    /// Fetch lyrics for the next uncached track in the warming queue
+    ///
+    /// The fetch runs in the background so slow providers don't freeze the
+    /// overlay; a tick that comes while it's still running is skipped.
+    fn warm_next_track(&mut self) {
+        if self.warming_task.as_ref().is_some_and(|task| !task.is_finished()) {
+            return;
+        }
+
+        while let Some(track) = self.warming_queue.pop_front() {
+            if self.lyrics_fetcher.is_cached(&track.id) || self.is_skipped(&track.id) {
+                continue;
+            }
+
+            log::debug!("Warming lyrics cache: {}", logged(&track.name));
+            let lyrics_fetcher = Arc::clone(&self.lyrics_fetcher);
+            self.warming_task = Some(tokio::spawn(async move {
+                let artist = track.artists.first().cloned().unwrap_or_default();
+                if let Err(e) = lyrics_fetcher
+                    .fetch_lyrics(&track.id, &artist, &track.name, Some(track.duration_ms))
+                    .await
+                {
+                    log::warn!("Failed to warm lyrics for {}: {}", logged(&track.name), e);
+                }
+            }));
+            break;
+        }
+
+        if self.warming_queue.is_empty() {
+            log::info!("Finished warming lyrics cache for playlist");
+        }
+    }
+
+    /// Look up the next track in the Spotify queue, once for both its uses
+    ///
+    /// With `show_up_next` it's reported as `UpNext`, and a failed lookup
+    /// hides the item rather than showing a stale track. With `prefetch` its
+    /// lyrics are fetched into the cache so the next change is instant. Runs
+    /// in the background so the current track's lyrics and the UI pump never
+    /// wait on it; prefetch failures are logged and otherwise ignored since
+    /// it's only an optimization.
+    async fn look_ahead(&self, show_up_next: bool, prefetch: bool) {
+        if !(show_up_next || prefetch) || !self.spotify_client.is_authenticated().await {
+            return;
+        }
+
+        let queue = self.spotify_client.get_queue();
+        let lyrics_fetcher = Arc::clone(&self.lyrics_fetcher);
+        let skipped_tracks = self.config.skipped_tracks.clone();
+        let event_tx = self.event_tx.clone();
+
+        tokio::spawn(async move {
+            let next_track = match queue.await {
+                Ok(queue) => queue.into_iter().next(),
+                Err(e) => {
+                    log::warn!("Failed to get playback queue: {}", e);
+                    None
+                }
+            };
+
+            if show_up_next {
+                if let Err(e) = event_tx.send(AppEvent::UpNext(next_track.clone())).await {
+                    log::error!("Failed to send UpNext event: {}", e);
+                }
+            }
+            if !prefetch {
+                return;
+            }
+
+            let Some(track) = next_track else {
+                log::debug!("Queue is empty, nothing to prefetch");
+                return;
+            };
+
+            if skipped_tracks.contains(&track.id) {
+                log::debug!("Next track is on the skip list, not prefetching");
+                return;
+            }
+
+            log::info!("Prefetching lyrics for next track: {}", logged(&track.name));
+            let artist = track.artists.first().cloned().unwrap_or_default();
+            if let Err(e) = lyrics_fetcher
+                .fetch_lyrics(&track.id, &artist, &track.name, Some(track.duration_ms))
+                .await
+            {
+                log::warn!("Failed to prefetch lyrics for {}: {}", logged(&track.name), e);
+            }
+        });
+    }
+
+    /// Show the next queued track in the menu
+    fn handle_up_next(&self, track: Option<TrackInfo>) -> Result<(), LyricsifyError> {
+        let up_next = track.map(|t| format!("{} — {}", t.name, t.artists.join(", ")));
+        self.menu_bar.update_up_next(up_next.as_deref())
+    }
+
+    /// Handle a playback progress report from the poller
+    fn handle_playback_progress(&mut self, progress: PlaybackProgress) -> Result<(), LyricsifyError> {
+        // With repeat-one the track id never changes, so detect the loop from
+        // the position wrapping around and reset the view to the top
+        if let Some((previous, _)) = &self.playback {
+            if progress.is_loop_restart(previous) {
+                log::info!("Track restarted from the beginning (repeat)");
+                if let Some(overlay) = self.ui_manager.overlay_window() {
+                    overlay.scroll_to_top()?;
+                }
+            }
+        }
+
+        self.playback = Some((progress, Instant::now()));
+        self.schedule_track_end_poll(&progress);
+
+        if let Some(track) = &self.current_track {
+            self.export_widget_state(WidgetState::playing(track, &progress));
+        }
+
+        self.update_progress_display()
+    }
+
+    /// Poll right after the track should end rather than up to a poll interval
+    /// later, so the next track's lyrics show up sooner
+    ///
+    /// Every progress report moves the deadline, which covers seeks; pausing
+    /// cancels it.
+    fn schedule_track_end_poll(&mut self, progress: &PlaybackProgress) {
+        match progress.remaining() {
+            None => self.track_end_poll = None,
+            Some(remaining) if remaining < MIN_TRACK_END_LEAD => {}
+            Some(remaining) => {
+                self.track_end_poll =
+                    Some(tokio::time::Instant::now() + remaining + TRACK_END_POLL_DELAY);
+            }
+        }
+    }
+
+    /// Share the playback state with companion widgets, if enabled
+    ///
+    /// Failures are only logged; the overlay doesn't depend on this.
+    fn export_widget_state(&self, state: WidgetState) {
+        if !self.config.widget_export {
+            return;
+        }
+        if let Err(e) = state.save(&self.config.widget_group_id) {
+            log::warn!("Failed to write widget state: {}", e);
+        }
+    }
+
+    /// Update the progress bar from the last report, extrapolating while playing
+    ///
+    /// While paused the last reported position is shown as-is, freezing the bar.
+    /// Skipped while the overlay is hidden; the position is recomputed from
+    /// the last report whenever it is shown again.
+    fn update_progress_display(&mut self) -> Result<(), LyricsifyError> {
+        let Some((progress, received_at)) = self.playback else {
+            return Ok(());
+        };
+        if !self.overlay_visible() {
+            return Ok(());
+        }
+
+        let position_ms = if progress.is_playing {
+            let elapsed_ms = received_at.elapsed().as_millis() as u64;
+            (progress.progress_ms + elapsed_ms).min(progress.duration_ms)
+        } else {
+            progress.progress_ms
+        };
+
+        if let Some(overlay) = self.ui_manager.overlay_window() {
+            overlay.update_progress(position_ms, progress.duration_ms)?;
+        }
+        Ok(())
+    }
+
+    /// Show a now playing notification, throttled to avoid notification storms
+    fn notify_now_playing(&mut self, track: &TrackInfo, has_lyrics: bool) {
+        if let Some(last) = self.last_notification {
+            if last.elapsed() < NOTIFICATION_MIN_INTERVAL {
+                log::debug!("Skipping now playing notification (throttled)");
+                return;
+            }
+        }
+        self.last_notification = Some(Instant::now());
+
+        let lyrics_status = if has_lyrics {
+            "Lyrics available"
+        } else {
+            "No lyrics found"
+        };
+        show_notification(
+            &track.name,
+            &format!("{} — {}", track.artists.join(", "), lyrics_status),
+        );
+    }
+
     /// Handle lyrics retrieved event by updating the UI
     fn handle_lyrics_retrieved(&mut self, lyrics: Option<String>) -> Result<(), LyricsifyError> {
+        if self.viewing_lookup {
+            log::debug!("Keeping pinned lookup instead of lyrics for the playing track");
+            return Ok(());
+        }
+
         if let Some(overlay) = self.ui_manager.overlay_window() {
             match lyrics {
                 Some(text) => {
@@ -193,15 +1036,89 @@ impl App {
                 }
                 None => {
                     log::info!("No lyrics available for this track");
-                    overlay.update_lyrics("Lyrics not available")?;
+                    let text = match &self.current_track {
+                        Some(track) => track.fallback_text(&self.config.no_lyrics_template),
+                        None => "Lyrics not available".to_string(),
+                    };
+                    overlay.update_lyrics(&text)?;
                 }
             }
         }
         Ok(())
     }
 
+    /// Ask for an "Artist - Title" and show its lyrics in the overlay
+    ///
+    /// The Spotify poller keeps running; the next track change replaces the
+    /// lookup unless `pin_manual_lookup` is set.
+    async fn handle_manual_lookup(&mut self) -> Result<(), LyricsifyError> {
+        let Some(query) = prompt_for_text(
+            "Look Up Lyrics",
+            "Enter the song as Artist - Title",
+            "Daft Punk - One More Time",
+            "Look Up",
+            self.mtm,
+        ) else {
+            return Ok(());
+        };
+
+        log::info!("Manual lyrics lookup: {}", logged(&query));
+        let lyrics = self.lyrics_fetcher.fetch_lyrics_by_query(&query).await;
+        let text = match lyrics.map(LyricsResult::into_text) {
+            Ok(Some(lyrics)) => format!("{}\n\n{}", query, lyrics),
+            Ok(None) => format!("{}\n\nLyrics not available", query),
+            Err(e) => {
+                log::warn!("Manual lookup of {} failed: {}", logged(&query), e);
+                show_notification("Lyricsify", &e.to_string());
+                return Ok(());
+            }
+        };
+
+        self.viewing_lookup = true;
+        self.menu_bar.update_lookup_state(true)?;
+        if let Some(overlay) = self.ui_manager.overlay_window() {
+            overlay.update_lyrics(&text)?;
+            overlay.scroll_to_top()?;
+        }
+        Ok(())
+    }
+
+    /// Leave a manual lookup and show the playing track's lyrics again
+    async fn handle_end_manual_lookup(&mut self) -> Result<(), LyricsifyError> {
+        self.viewing_lookup = false;
+        self.menu_bar.update_lookup_state(false)?;
+
+        match self.current_track.clone() {
+            // Normally a cache hit, since the track was fetched when it started
+            Some(track) if !self.is_skipped(&track.id) => {
+                // Or still waiting on "Fetch Lyrics Now" in on-demand mode
+                if self.awaiting_fetch_request(&track.id) {
+                    let now_playing = format!("{} by {}", track.name, track.artists.join(", "));
+                    return self.update_overlay_text(&now_playing);
+                }
+
+                let artist = track.artists.first().cloned().unwrap_or_default();
+                let lyrics = self
+                    .lyrics_fetcher
+                    .fetch_lyrics(&track.id, &artist, &track.name, Some(track.duration_ms))
+                    .await?;
+                self.handle_lyrics_retrieved(lyrics.into_text())
+            }
+            Some(_) => self.update_overlay_text("No lyrics (skipped)"),
+            None => self.update_overlay_text("Nothing playing"),
+        }
+    }
+
+    /// Replace the overlay text, if there is an overlay
+    fn update_overlay_text(&self, text: &str) -> Result<(), LyricsifyError> {
+        if let Some(overlay) = self.ui_manager.overlay_window() {
+            overlay.update_lyrics(text)?;
+        }
+        Ok(())
+    }
+
     /// Handle toggle overlay event
-    fn handle_toggle_overlay(&mut self) -> Result<(), LyricsifyError> {
+    async fn handle_toggle_overlay(&mut self) -> Result<(), LyricsifyError> {
         if let Some(overlay) = self.ui_manager.overlay_window() {
             let is_visible = overlay.is_visible();
             
@@ -211,15 +1128,400 @@ impl App {
                 self.menu_bar.update_visibility_state(false)?;
             } else {
                 log::info!("Showing overlay");
+                self.anchor_overlay();
                 overlay.show()?;
                 self.menu_bar.update_visibility_state(true)?;
             }
+
+            // Keep our copy in sync so saving it doesn't undo the overlay's change
+            self.config.overlay_visible = !is_visible;
+
+            if !is_visible {
+                self.catch_up_after_show().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Bring the overlay up to date after it was hidden
+    async fn catch_up_after_show(&mut self) -> Result<(), LyricsifyError> {
+        self.update_progress_display()?;
+
+        if std::mem::take(&mut self.fetch_deferred) {
+            if let Some(track) = self.current_track.clone() {
+                log::info!("Overlay shown, fetching deferred lyrics for {}", logged(&track.name));
+                self.fetch_track_lyrics(track).await?;
+                self.look_ahead(false, self.config.prefetch_next_track).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Move the overlay below the menu bar icon, if configured
+    fn anchor_overlay(&self) {
+        if !self.config.anchor_to_menu_bar_icon {
+            return;
+        }
+        let Some(overlay) = self.ui_manager.overlay_window() else {
+            return;
+        };
+        match self.menu_bar.icon_frame() {
+            Some((icon_frame, bounds)) => overlay.anchor_below(icon_frame, bounds),
+            None => log::info!("Menu bar icon is off screen, leaving the overlay where it was"),
+        }
+    }
+
+    /// Whether the overlay exists and is on screen
+    fn overlay_visible(&self) -> bool {
+        self.ui_manager
+            .overlay_window()
+            .is_some_and(|overlay| overlay.is_visible())
+    }
+
+    /// Step the overlay opacity and briefly show the new value on it
+    fn handle_adjust_opacity(&mut self, delta: f64) {
+        if let Some(overlay) = self.ui_manager.overlay_window() {
+            let opacity = overlay.adjust_opacity(delta);
+            overlay.flash_badge(&format!("Opacity {:.0}%", opacity * 100.0));
+
+            // Keep our copy in sync so saving it doesn't undo the overlay's
+            // change; the overlay saves it, debounced, as the steps come in
+            self.config.overlay_opacity = Some(opacity);
+        }
+    }
+
+    /// Copy the overlay's window position into the app's config
+    ///
+    /// Only the overlay knows where it was dragged to, so without this the
+    /// app's next save would put the window back where it was at launch.
+    fn sync_overlay_position(&mut self) {
+        if let Some(position) = self
+            .ui_manager
+            .overlay_window()
+            .and_then(|overlay| overlay.saved_position())
+        {
+            self.config.window_position = position;
+        }
+    }
+
+    /// Dim the overlay while another app is in front, if enabled
+    fn handle_focus_changed(&self, focused: bool) {
+        if !self.config.dim_when_unfocused {
+            return;
+        }
+        if let Some(overlay) = self.ui_manager.overlay_window() {
+            overlay.set_dimmed(!focused, self.config.dimmed_opacity);
+        }
+    }
+
+    /// Handle a request to hide the overlay (Escape pressed on it)
+    async fn handle_hide_overlay(&mut self) -> Result<(), LyricsifyError> {
+        if self.overlay_visible() {
+            self.handle_toggle_overlay().await?;
+        }
+        Ok(())
+    }
+
+    /// Handle toggle now playing notifications event
+    fn handle_toggle_notifications(&mut self) -> Result<(), LyricsifyError> {
+        let enabled = !self.config.now_playing_notifications;
+        log::info!("Now playing notifications {}", if enabled { "enabled" } else { "disabled" });
+
+        self.config.now_playing_notifications = enabled;
+        self.config_save.request();
+        self.menu_bar.update_notifications_state(enabled)?;
+        Ok(())
+    }
+
+    /// Whether lyrics for a track are skipped by user request
+    fn is_skipped(&self, track_id: &str) -> bool {
+        self.config.skipped_tracks.iter().any(|id| id == track_id)
+    }
+
+    /// Add the current track to the skip list
+    fn handle_skip_current_track(&mut self) -> Result<(), LyricsifyError> {
+        let Some(track) = &self.current_track else {
+            log::warn!("No current track to skip");
+            return Ok(());
+        };
+
+        if !self.is_skipped(&track.id) {
+            log::info!("Never fetching lyrics for {} again", logged(&track.name));
+            self.config.skipped_tracks.push(track.id.clone());
+            self.config_save.request();
+        }
+
+        if let Some(overlay) = self.ui_manager.overlay_window() {
+            overlay.update_lyrics("No lyrics (skipped)")?;
+        }
+        self.menu_bar.update_skip_state(false, self.config.skipped_tracks.len())
+    }
+
+    /// Empty the skip list so lyrics are fetched again for those tracks
+    ///
+    /// The current track's lyrics are fetched on its next play.
+    fn handle_clear_skipped_tracks(&mut self) -> Result<(), LyricsifyError> {
+        log::info!("Clearing {} skipped tracks", self.config.skipped_tracks.len());
+        self.config.skipped_tracks.clear();
+        self.config_save.request();
+
+        self.menu_bar.update_skip_state(self.current_track.is_some(), 0)
+    }
+
+    /// Run the setup checks and show the report in the overlay
+    async fn handle_run_diagnostics(&mut self) -> Result<(), LyricsifyError> {
+        if let Some(overlay) = self.ui_manager.overlay_window() {
+            overlay.update_lyrics("Running diagnostics…")?;
+        }
+
+        let report = diagnostics::run(&self.spotify_client, &self.lyrics_fetcher).await;
+
+        if let Some(overlay) = self.ui_manager.overlay_window() {
+            overlay.update_lyrics(&report.summary())?;
+        }
+        let message = if report.all_passed() {
+            "All checks passed".to_string()
+        } else {
+            format!(
+                "{} of {} checks failed, see the overlay for details",
+                report.checks.len() - report.passed(),
+                report.checks.len()
+            )
+        };
+        show_notification("Lyricsify Diagnostics", &message);
+
+        Ok(())
+    }
+
+    /// Step the log level and say which level is now active
+    fn handle_cycle_log_level(&self) {
+        let level = log_level::cycle();
+        show_notification("Lyricsify", &format!("Log level: {}", level));
+    }
+
+    /// Copy a redacted diagnostics bundle (checks, config, stats, log tail) to the clipboard
+    async fn handle_copy_diagnostics(&mut self) {
+        let report = diagnostics::run(&self.spotify_client, &self.lyrics_fetcher).await;
+        let bundle = diagnostics::bundle(
+            &report,
+            &self.config,
+            &self.stats_lines(),
+            &log_tail::recent_lines(),
+        );
+
+        if copy_to_clipboard(&bundle) {
+            show_notification("Lyricsify Diagnostics", "Diagnostics copied to the clipboard");
+        } else {
+            log::error!("Failed to copy diagnostics to the clipboard");
+        }
+    }
+
+    /// Save the settings to a file of the user's choosing
+    fn handle_export_settings(&self) {
+        let Some(path) = choose_save_path(settings_transfer::EXPORT_FILE_NAME, self.mtm) else {
+            return;
+        };
+
+        let result = settings_transfer::export(&self.config).and_then(|(json, omitted)| {
+            std::fs::write(&path, json)?;
+            Ok(omitted)
+        });
+        match result {
+            Ok(omitted) if omitted.is_empty() => {
+                log::info!("Exported settings to {:?}", path);
+                show_notification("Lyricsify", "Settings exported");
+            }
+            Ok(omitted) => {
+                log::info!("Exported settings to {:?} without {}", path, omitted.join(", "));
+                show_notification(
+                    "Lyricsify",
+                    &format!(
+                        "Settings exported without {}, which may hold secrets. \
+                         Set them up again after importing.",
+                        omitted.join(", ")
+                    ),
+                );
+            }
+            Err(e) => {
+                log::error!("Failed to export settings: {}", e);
+                show_notification("Lyricsify", &format!("Couldn't export settings: {}", e));
+            }
+        }
+    }
+
+    /// Merge settings from a file of the user's choosing
+    ///
+    /// Most settings are read at launch, so they take full effect on restart.
+    fn handle_import_settings(&mut self) -> Result<(), LyricsifyError> {
+        let Some(path) = choose_open_path(self.mtm) else {
+            return Ok(());
+        };
+
+        // The import keeps the local window position, so bring ours up to date
+        self.sync_overlay_position();
+        let result = std::fs::read_to_string(&path)
+            .map_err(LyricsifyError::from)
+            .and_then(|json| settings_transfer::import(&json, &self.config));
+        let config = match result {
+            Ok(config) => config,
+            Err(e) => {
+                log::error!("Failed to import settings from {:?}: {}", path, e);
+                show_notification("Lyricsify", &format!("Couldn't import settings: {}", e));
+                return Ok(());
+            }
+        };
+
+        log::info!("Imported settings from {:?}", path);
+        self.config = config;
+        self.config_save.take_pending();
+        let saved = match self.config.save() {
+            Ok(()) => true,
+            Err(e) => {
+                log::error!("Failed to save imported settings: {}", e);
+                false
+            }
+        };
+        if let Some(overlay) = self.ui_manager.overlay_window() {
+            overlay.replace_config(self.config.clone());
+        }
+
+        let can_skip = self
+            .current_track
+            .as_ref()
+            .is_some_and(|track| !self.is_skipped(&track.id));
+        self.menu_bar
+            .update_notifications_state(self.config.now_playing_notifications)?;
+        self.menu_bar.update_skip_state(can_skip, self.config.skipped_tracks.len())?;
+
+        if saved {
+            show_notification(
+                "Lyricsify",
+                "Settings imported. Restart Lyricsify to apply all of them.",
+            );
+        } else {
+            show_notification(
+                "Lyricsify",
+                "Settings imported, but couldn't be saved, so they're lost when Lyricsify quits.",
+            );
         }
         Ok(())
     }
 
+    /// Human-readable lyrics provider and Spotify request counters
+    fn stats_lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .lyrics_fetcher
+            .provider_stats()
+            .iter()
+            .map(|(provider, stats)| {
+                format!(
+                    "Lyrics provider {}: {} requests, {} failed, {} slow, {} ms average",
+                    provider,
+                    stats.requests,
+                    stats.failures,
+                    stats.slow_requests,
+                    stats.average_latency().as_millis()
+                )
+            })
+            .collect();
+
+        let spotify_stats = self.spotify_client.scheduler_stats();
+        lines.push(format!(
+            "Spotify requests: {} sent, {} delayed by rate limiting, {} rate limited (429)",
+            spotify_stats.granted, spotify_stats.delayed, spotify_stats.rate_limited
+        ));
+        lines
+    }
+
+    /// Hand the current counters to the metrics server, if running
+    fn publish_metrics(&self) {
+        let Some(server) = &self.metrics_server else {
+            return;
+        };
+        server.publish(metrics::render(&MetricsInput {
+            polls: self.spotify_client.poll_stats(),
+            spotify: self.spotify_client.scheduler_stats(),
+            cache: self.lyrics_fetcher.cache_stats(),
+            providers: &self.lyrics_fetcher.provider_stats(),
+        }));
+    }
+
+    /// Refresh the Spotify token on demand and report the outcome
+    async fn handle_refresh_token(&mut self) -> Result<(), LyricsifyError> {
+        match self.spotify_client.refresh_token().await {
+            Ok(()) => show_notification("Lyricsify", "Spotify token refreshed"),
+            Err(e) => {
+                log::warn!("Manual token refresh failed: {}", e);
+                show_notification("Lyricsify", &format!("Token refresh failed: {}", e));
+            }
+        }
+
+        // A rejected refresh token drops the session
+        let authenticated = self.spotify_client.is_authenticated().await;
+        self.menu_bar.update_auth_state(authenticated)
+    }
+
+    /// Restart the poll loop, replacing it if it is still running
+    async fn handle_restart_polling(&mut self) -> Result<(), LyricsifyError> {
+        if !self.spotify_client.is_authenticated().await {
+            log::warn!("Not restarting polling, not authenticated");
+            return Ok(());
+        }
+
+        log::info!("Restarting Spotify polling from the menu");
+        self.spotify_client
+            .start_polling(self.event_tx.clone(), self.shutting_down.clone());
+        show_notification("Lyricsify", "Reconnecting to Spotify");
+        Ok(())
+    }
+
+    /// Tell the user polling has stopped and how to restart it
+    fn handle_polling_stopped(&mut self) -> Result<(), LyricsifyError> {
+        log::error!("Spotify polling stopped after repeated crashes");
+
+        if let Some(overlay) = self.ui_manager.overlay_window() {
+            overlay.update_lyrics(
+                "Lost connection to Spotify — choose Restart Spotify Connection from the menu",
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Catch up after the Mac wakes from sleep
+    ///
+    /// The token has likely expired and the track may have changed while
+    /// asleep, so refresh the token if needed and poll straight away rather
+    /// than showing the old song until the next tick.
+    async fn handle_system_woke(&mut self) -> Result<(), LyricsifyError> {
+        if !self.spotify_client.is_authenticated().await {
+            return Ok(());
+        }
+        log::info!("System woke from sleep, refreshing playback state");
+
+        if !self.spotify_client.is_token_valid().await {
+            if let Err(e) = self.spotify_client.refresh_token().await {
+                log::warn!("Token refresh after wake failed: {}", e);
+            }
+            // A rejected refresh token drops the session
+            let authenticated = self.spotify_client.is_authenticated().await;
+            self.menu_bar.update_auth_state(authenticated)?;
+            if !authenticated {
+                return Ok(());
+            }
+        }
+
+        self.spotify_client.poll_now();
+        Ok(())
+    }
+
     /// Handle authenticate event
     async fn handle_authenticate(&mut self) -> Result<(), LyricsifyError> {
+        // Signing in from the menu always opens the browser
+        self.start_authentication(true)
+    }
+
+    /// Begin the sign-in flow, opening the browser or copying the URL
+    fn start_authentication(&mut self, open_browser: bool) -> Result<(), LyricsifyError> {
         log::info!("Starting authentication flow");
 
         // Get the authorization URL
@@ -228,9 +1530,15 @@ impl App {
         log::info!("Please visit this URL to authenticate:");
         log::info!("{}", auth_url);
         
-        // Open the URL in the default browser
-        if let Err(e) = open_url(&auth_url) {
-            log::error!("Failed to open browser: {}", e);
+        if open_browser {
+            // Open the URL in the default browser
+            if let Err(e) = open_url(&auth_url) {
+                log::error!("Failed to open browser: {}", e);
+            }
+        } else if copy_to_clipboard(&auth_url) {
+            show_notification("Lyricsify", "Spotify sign-in URL copied to the clipboard");
+        } else {
+            log::error!("Failed to copy the sign-in URL to the clipboard");
         }
 
         // In a real implementation, we would:
@@ -260,18 +1568,62 @@ impl App {
     }
 
     /// Perform graceful shutdown
-    fn shutdown(&mut self) -> Result<(), LyricsifyError> {
+    ///
+    /// Everything persisted is written out, each independently, so a failure
+    /// to save one (logged) never stops the others.
+    fn shutdown(&mut self) {
         log::info!("Shutting down application");
+        self.shutting_down.trigger();
+
+        for line in self.stats_lines() {
+            log::info!("{}", line);
+        }
+
+        // The lyrics may have been fetched after the track change saved it
+        let last_track = self.current_track.as_ref().map(|track| LastTrack {
+            track_id: track.id.clone(),
+            name: track.name.clone(),
+            artists: track.artists.clone(),
+            lyrics: self.lyrics_fetcher.peek_cached(&track.id),
+        });
+
+        // The app's copy is written last and has the final say
+        self.sync_overlay_position();
+        self.config_save.take_pending();
+
+        let config_dir = AppConfig::config_dir();
+        let mut steps = Vec::new();
+        if let Some(overlay) = self.ui_manager.overlay_window() {
+            steps.push(FlushStep::new("overlay settings", || overlay.flush_config()));
+        }
+        match &config_dir {
+            Ok(dir) => {
+                steps.extend(shutdown::config_dir_steps(dir, &self.config, last_track.as_ref()))
+            }
+            Err(e) => {
+                let message = e.to_string();
+                steps.push(FlushStep::new("configuration", move || {
+                    Err(LyricsifyError::ConfigError(message))
+                }));
+            }
+        }
+        // Widgets would otherwise show the track as playing forever
+        if self.config.widget_export {
+            steps.push(FlushStep::new("widget state", || {
+                WidgetState::stopped().save(&self.config.widget_group_id)
+            }));
+        }
 
-        // Save configuration
-        self.config.save()?;
-        log::info!("Configuration saved");
+        let failed = shutdown::flush_all(steps);
 
         // Clean up resources
         // (Tokio tasks will be automatically cancelled when the runtime shuts down)
 
-        log::info!("Shutdown complete");
-        Ok(())
+        if failed.is_empty() {
+            log::info!("Shutdown complete");
+        } else {
+            log::warn!("Shutdown complete, but failed to save: {}", failed.join(", "));
+        }
     }
 }
 
@@ -0,0 +1,82 @@
+use objc2::rc::Retained;
+use objc2::runtime::Sel;
+use objc2::ClassType;
+use objc2_app_kit::{NSImage, NSTextField, NSVisualEffectMaterial, NSWorkspace};
+use objc2_foundation::{MainThreadMarker, NSOperatingSystemVersion, NSProcessInfo, NSString};
+
+use crate::theme::ThemeMaterial;
+
+// Calling a selector the runtime doesn't know raises an Objective-C exception,
+// which aborts the app. AppKit calls that need a newer macOS than the oldest
+// supported one go through the helpers below, so the fallbacks live in one place.
+
+/// Whether the class `C` implements the class method `selector`
+fn class_responds_to<C: ClassType>(selector: Sel) -> bool {
+    C::class().metaclass().responds_to(selector)
+}
+
+/// Whether instances of `C` implement `selector`
+fn instances_respond_to<C: ClassType>(selector: Sel) -> bool {
+    C::class().responds_to(selector)
+}
+
+/// Whether the running macOS is at least `major.minor`
+fn os_at_least(major: isize, minor: isize) -> bool {
+    NSProcessInfo::processInfo().isOperatingSystemAtLeastVersion(NSOperatingSystemVersion {
+        majorVersion: major,
+        minorVersion: minor,
+        patchVersion: 0,
+    })
+}
+
+/// Whether SF Symbol images can be loaded (macOS 11+)
+pub fn sf_symbols_available() -> bool {
+    class_responds_to::<NSImage>(objc2::sel!(
+        imageWithSystemSymbolName:accessibilityDescription:
+    ))
+}
+
+/// Whether the user asked for reduced motion (false where the setting doesn't exist)
+pub fn reduce_motion_enabled() -> bool {
+    instances_respond_to::<NSWorkspace>(objc2::sel!(accessibilityDisplayShouldReduceMotion))
+        && unsafe { NSWorkspace::sharedWorkspace().accessibilityDisplayShouldReduceMotion() }
+}
+
+/// The blur material for a theme setting
+///
+/// The semantic materials arrived in macOS 10.14; earlier versions get the
+/// closest of the original light/dark materials.
+#[allow(deprecated)]
+pub fn visual_effect_material(material: ThemeMaterial) -> NSVisualEffectMaterial {
+    if !os_at_least(10, 14) {
+        return match material {
+            ThemeMaterial::Popover => NSVisualEffectMaterial::Light,
+            _ => NSVisualEffectMaterial::Dark,
+        };
+    }
+
+    match material {
+        ThemeMaterial::Hud => NSVisualEffectMaterial::HUDWindow,
+        ThemeMaterial::Popover => NSVisualEffectMaterial::Popover,
+        ThemeMaterial::Menu => NSVisualEffectMaterial::Menu,
+        ThemeMaterial::UnderWindow => NSVisualEffectMaterial::UnderWindowBackground,
+    }
+}
+
+/// A non-editable text label, built by hand where `labelWithString:` is missing
+pub fn label(text: &str, mtm: MainThreadMarker) -> Retained<NSTextField> {
+    let text = NSString::from_str(text);
+    unsafe {
+        if class_responds_to::<NSTextField>(objc2::sel!(labelWithString:)) {
+            return NSTextField::labelWithString(&text, mtm);
+        }
+
+        let label = NSTextField::new(mtm);
+        label.setStringValue(&text);
+        label.setBezeled(false);
+        label.setDrawsBackground(false);
+        label.setEditable(false);
+        label.setSelectable(false);
+        label
+    }
+}
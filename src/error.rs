@@ -17,8 +17,13 @@ pub enum LyricsifyError {
     #[error("Configuration error: {0}")]
     ConfigError(String),
 
+    /// A file wasn't saved because the config directory can't be written;
+    /// already warned about once, so routine saves can ignore it
+    #[error("The config directory isn't writable, so changes are kept in memory only")]
+    ConfigDirUnwritable,
+
     #[error("Network error: {0}")]
-    NetworkError(#[from] reqwest::Error),
+    NetworkError(reqwest::Error),
 
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
@@ -31,3 +36,30 @@ pub enum LyricsifyError {
 }
 
 pub type Result<T> = std::result::Result<T, LyricsifyError>;
+
+impl From<reqwest::Error> for LyricsifyError {
+    /// Lyrics lookup URLs spell out the artist and title, so the URL is
+    /// dropped to keep it out of logged errors
+    fn from(error: reqwest::Error) -> Self {
+        LyricsifyError::NetworkError(error.without_url())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_network_errors_leave_out_the_url() {
+        // Nothing listens on port 1, so this fails without leaving the machine
+        let error = reqwest::Client::new()
+            .get("http://127.0.0.1:1/v1/Aphex%20Twin/Windowlicker")
+            .send()
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Windowlicker"));
+
+        let error = LyricsifyError::from(error);
+        assert!(!error.to_string().contains("Windowlicker"));
+    }
+}
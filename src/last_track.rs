@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::AppConfig;
+use crate::error::Result;
+use crate::log_privacy::logged;
+
+/// Name of the last track file in the config directory
+const FILE_NAME: &str = "last_track.json";
+
+/// The last track seen by the poller, persisted so the overlay has something
+/// to show immediately on the next launch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LastTrack {
+    pub track_id: String,
+    pub name: String,
+    pub artists: Vec<String>,
+    pub lyrics: Option<String>,
+}
+
+impl LastTrack {
+    /// Get the path to the last track file
+    fn file_path() -> Result<PathBuf> {
+        Ok(AppConfig::config_dir()?.join(FILE_NAME))
+    }
+
+    /// Load the last track from disk, if one was saved
+    pub fn load() -> Option<Self> {
+        let path = Self::file_path().ok()?;
+        let contents = fs::read_to_string(&path).ok()?;
+
+        match serde_json::from_str(&contents) {
+            Ok(last_track) => Some(last_track),
+            Err(e) => {
+                log::warn!("Failed to parse last track file ({}), ignoring it", e);
+                None
+            }
+        }
+    }
+
+    /// Save this track as the last one seen
+    pub fn save(&self) -> Result<()> {
+        self.save_in(&AppConfig::config_dir()?)
+    }
+
+    /// Save this track to `dir`, which is the config directory outside tests
+    pub(crate) fn save_in(&self, dir: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        AppConfig::write_config_file(dir, FILE_NAME, &json)?;
+        log::debug!("Saved last track: {}", logged(&self.track_id));
+        Ok(())
+    }
+
+    /// Text shown in the overlay until the first poll reports the current track
+    pub fn stale_display_text(&self) -> String {
+        format!(
+            "{} — {} (last played, may be stale)\n\n{}",
+            self.name,
+            self.artists.join(", "),
+            self.lyrics.as_deref().unwrap_or("Lyrics not available")
+        )
+    }
+}
@@ -0,0 +1,83 @@
+use std::sync::OnceLock;
+
+use log::LevelFilter;
+
+/// Level the app starts at when `RUST_LOG` isn't set
+pub const DEFAULT_LEVEL: LevelFilter = LevelFilter::Info;
+
+/// This crate's log target prefix; only its records get more verbose when cycling
+const CRATE_TARGET: &str = env!("CARGO_CRATE_NAME");
+
+/// Level logging started at, which cycling past `Trace` returns to
+static START_LEVEL: OnceLock<LevelFilter> = OnceLock::new();
+
+/// Install the logger, writing to `target`, at the level `RUST_LOG` asks for
+///
+/// The filters from `RUST_LOG` are kept as they are, except that this
+/// crate's records get through the logger at every level (unless `RUST_LOG`
+/// sets one for it). The global max level starts at what `RUST_LOG` allows,
+/// so nothing more is logged until the user cycles the level.
+pub fn init(target: env_logger::Target) {
+    let start_level = env_builder().build().filter();
+
+    let mut builder = env_builder();
+    let crate_filtered = std::env::var("RUST_LOG")
+        .is_ok_and(|filters| filters.contains(CRATE_TARGET));
+    if !crate_filtered {
+        builder.filter_module(CRATE_TARGET, LevelFilter::Trace);
+    }
+    builder.target(target).init();
+
+    let _ = START_LEVEL.set(start_level);
+    log::set_max_level(start_level);
+}
+
+/// A logger builder with the filters from `RUST_LOG`, or `DEFAULT_LEVEL` if unset
+fn env_builder() -> env_logger::Builder {
+    let mut builder = env_logger::Builder::from_default_env();
+    if std::env::var_os("RUST_LOG").is_none() {
+        builder.filter_level(DEFAULT_LEVEL);
+    }
+    builder
+}
+
+/// Step to the next log level: Info → Debug → Trace → back to the start level
+///
+/// The logger passes this crate's records at every level, so changing the
+/// global max level takes effect immediately without rebuilding it.
+pub fn cycle() -> LevelFilter {
+    let start_level = START_LEVEL.get().copied().unwrap_or(DEFAULT_LEVEL);
+    let level = next_level(log::max_level(), start_level);
+    log::set_max_level(level);
+    log::info!("Log level set to {}", level);
+    level
+}
+
+/// The level after `level` in the cycle
+///
+/// Levels quieter than Info step up to it, and `Trace` goes back to
+/// `start_level`, so cycling never drops below where logging started.
+fn next_level(level: LevelFilter, start_level: LevelFilter) -> LevelFilter {
+    match level {
+        LevelFilter::Trace => start_level,
+        LevelFilter::Debug => LevelFilter::Trace,
+        LevelFilter::Info => LevelFilter::Debug,
+        _ => LevelFilter::Info,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels_cycle_back_to_start() {
+        assert_eq!(next_level(LevelFilter::Info, LevelFilter::Info), LevelFilter::Debug);
+        assert_eq!(next_level(LevelFilter::Debug, LevelFilter::Info), LevelFilter::Trace);
+        assert_eq!(next_level(LevelFilter::Trace, LevelFilter::Info), LevelFilter::Info);
+
+        // Started quieter via RUST_LOG
+        assert_eq!(next_level(LevelFilter::Warn, LevelFilter::Warn), LevelFilter::Info);
+        assert_eq!(next_level(LevelFilter::Trace, LevelFilter::Warn), LevelFilter::Warn);
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::sync::Mutex;
+
+/// Number of recent log lines kept for diagnostics
+const MAX_LINES: usize = 200;
+
+static RECENT_LINES: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Log target that writes to stderr and keeps the most recent lines in memory
+///
+/// Installed as the env_logger pipe target so bug reports can include the
+/// log leading up to a problem without asking users to find the terminal.
+#[derive(Default)]
+pub struct TeeWriter {
+    /// Bytes of a line that hasn't been terminated yet
+    pending: Vec<u8>,
+}
+
+impl Write for TeeWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        io::stderr().write_all(buf)?;
+
+        self.pending.extend_from_slice(buf);
+        while let Some(newline) = self.pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=newline).collect();
+            push_line(String::from_utf8_lossy(&line[..newline]).into_owned());
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stderr().flush()
+    }
+}
+
+/// Remember a log line, dropping the oldest once `MAX_LINES` are kept
+fn push_line(line: String) {
+    let mut lines = RECENT_LINES.lock().unwrap_or_else(|e| e.into_inner());
+    if lines.len() == MAX_LINES {
+        lines.pop_front();
+    }
+    lines.push_back(line);
+}
+
+/// The most recent log lines, oldest first
+pub fn recent_lines() -> Vec<String> {
+    let lines = RECENT_LINES.lock().unwrap_or_else(|e| e.into_inner());
+    lines.iter().cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_only_the_most_recent_complete_lines() {
+        let mut writer = TeeWriter::default();
+        for i in 0..MAX_LINES + 5 {
+            writeln!(writer, "line {}", i).unwrap();
+        }
+        writer.write_all(b"partial").unwrap();
+
+        let lines = recent_lines();
+        assert_eq!(lines.len(), MAX_LINES);
+        assert_eq!(lines.first().map(String::as_str), Some("line 5"));
+        assert_eq!(
+            lines.last().map(String::as_str),
+            Some(format!("line {}", MAX_LINES + 4).as_str())
+        );
+    }
+}
@@ -1,24 +1,69 @@
 mod app_core;
+mod compat;
 mod config;
+mod diagnostics;
 mod error;
+mod last_track;
+mod log_level;
+mod log_privacy;
+mod log_tail;
 mod lyrics_fetcher;
+mod metrics;
+mod request_scheduler;
+mod settings_transfer;
+mod shutdown;
 mod spotify_client;
+mod theme;
+mod tui;
 mod ui_manager;
+mod widget_export;
 
 use app_core::App;
 use error::LyricsifyError;
+use objc2_foundation::MainThreadMarker;
 
-#[tokio::main]
-async fn main() -> Result<(), LyricsifyError> {
-    // Initialize logging
-    env_logger::Builder::from_default_env()
-        .filter_level(log::LevelFilter::Info)
-        .init();
+/// Threading model
+///
+/// AppKit objects (the overlay window and status item) may only be created
+/// and used on the main thread. The Tokio runtime's worker threads run the
+/// background tasks (Spotify polling, menu event forwarding), while the `App`
+/// itself, which owns all UI objects, is driven by `block_on` on the main
+/// thread. UI objects are not `Send`, so they can never end up on a worker.
+///
+/// `NSApplication::run` is never called, since it would block the main
+/// thread forever. Instead the app's event loop pumps pending AppKit events
+/// every frame (see `UIManager::pump_events`), so both `AppEvent`s and Cocoa
+/// events are serviced from the same thread.
+fn main() -> Result<(), LyricsifyError> {
+    // Initialize logging; still logs to stderr, but keeps recent lines for
+    // diagnostics bundles
+    log_level::init(env_logger::Target::Pipe(Box::new(log_tail::TeeWriter::default())));
 
     log::info!("Starting Lyricsify...");
 
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?;
+
+    // The terminal frontend needs none of AppKit
+    if std::env::args().skip(1).any(|arg| arg == tui::TUI_FLAG) {
+        // Log lines on stderr would be drawn over the lyrics
+        log::set_max_level(log::LevelFilter::Warn);
+        return runtime.block_on(tui::run());
+    }
+
+    let mtm = MainThreadMarker::new().ok_or_else(|| {
+        LyricsifyError::UIError("Lyricsify must be started on the main thread".to_string())
+    })?;
+
+    // block_on polls the app future on the calling (main) thread
+    runtime.block_on(run_app(mtm))
+}
+
+/// Create, initialize, and run the application until it quits
+async fn run_app(mtm: MainThreadMarker) -> Result<(), LyricsifyError> {
     // Create and initialize the application
-    let mut app = App::new()?;
+    let mut app = App::new(mtm)?;
     log::info!("Application created successfully");
 
     // Initialize components (load config, authenticate, start polling)
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::lyrics_fetcher::{CacheStats, ProviderStats};
+use crate::request_scheduler::SchedulerStats;
+use crate::spotify_client::PollStats;
+
+/// Counters gathered from every component for one export
+pub struct MetricsInput<'a> {
+    pub polls: PollStats,
+    pub spotify: SchedulerStats,
+    pub cache: CacheStats,
+    pub providers: &'a HashMap<&'static str, ProviderStats>,
+}
+
+/// Render counters in the Prometheus text exposition format
+pub fn render(input: &MetricsInput) -> String {
+    let mut out = String::new();
+
+    counter(&mut out, "lyricsify_polls_total", "Spotify playback polls", input.polls.polls);
+    counter(
+        &mut out,
+        "lyricsify_poll_failures_total",
+        "Spotify playback polls that failed after retries",
+        input.polls.failures,
+    );
+    counter(
+        &mut out,
+        "lyricsify_spotify_requests_total",
+        "Spotify API requests sent",
+        input.spotify.granted,
+    );
+    counter(
+        &mut out,
+        "lyricsify_spotify_requests_delayed_total",
+        "Spotify API requests delayed by the rate limiter",
+        input.spotify.delayed,
+    );
+    counter(
+        &mut out,
+        "lyricsify_spotify_rate_limited_total",
+        "Spotify API 429 responses",
+        input.spotify.rate_limited,
+    );
+    counter(&mut out, "lyricsify_cache_hits_total", "Lyrics cache hits", input.cache.hits);
+    counter(&mut out, "lyricsify_cache_misses_total", "Lyrics cache misses", input.cache.misses);
+
+    // Sorted so the output is stable between scrapes
+    let mut providers: Vec<_> = input.providers.iter().collect();
+    providers.sort_by_key(|(name, _)| **name);
+
+    let families: [(&str, &str, fn(&ProviderStats) -> String); 4] = [
+        ("lyricsify_provider_requests_total", "Lyrics provider requests", |s| {
+            s.requests.to_string()
+        }),
+        ("lyricsify_provider_failures_total", "Lyrics provider failed requests", |s| {
+            s.failures.to_string()
+        }),
+        ("lyricsify_provider_slow_requests_total", "Lyrics provider slow requests", |s| {
+            s.slow_requests.to_string()
+        }),
+        ("lyricsify_provider_latency_seconds_total", "Lyrics provider time spent", |s| {
+            s.total_latency.as_secs_f64().to_string()
+        }),
+    ];
+    for (name, help, value) in families {
+        let _ = writeln!(out, "# HELP {} {}\n# TYPE {} counter", name, help, name);
+        for (provider, stats) in &providers {
+            let _ = writeln!(out, "{}{{provider=\"{}\"}} {}", name, provider, value(stats));
+        }
+    }
+
+    out
+}
+
+/// Append a single unlabelled counter
+fn counter(out: &mut String, name: &str, help: &str, value: u64) {
+    let _ = writeln!(out, "# HELP {} {}\n# TYPE {} counter", name, help, name);
+    let _ = writeln!(out, "{} {}", name, value);
+}
+
+/// Serves the latest rendered metrics on `http://127.0.0.1:<port>/metrics`
+///
+/// The app renders the counters into `latest` periodically, so the server
+/// never has to reach into app state.
+pub struct MetricsServer {
+    latest: Arc<Mutex<String>>,
+}
+
+impl MetricsServer {
+    /// Bind to localhost and start serving in the background
+    pub async fn start(port: u16) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port)).await?;
+        log::info!("Serving metrics on http://127.0.0.1:{}/metrics", port);
+
+        let latest = Arc::new(Mutex::new(String::new()));
+        let body = Arc::clone(&latest);
+        tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        let body = Arc::clone(&body);
+                        tokio::spawn(async move {
+                            if let Err(e) = serve(stream, &body).await {
+                                log::debug!("Metrics request failed: {}", e);
+                            }
+                        });
+                    }
+                    Err(e) => log::warn!("Metrics server accept failed: {}", e),
+                }
+            }
+        });
+
+        Ok(Self { latest })
+    }
+
+    /// Replace the metrics returned to scrapers
+    pub fn publish(&self, metrics: String) {
+        if let Ok(mut latest) = self.latest.lock() {
+            *latest = metrics;
+        }
+    }
+}
+
+/// Answer one HTTP request: the metrics for `GET /metrics`, 404 otherwise
+async fn serve(mut stream: TcpStream, body: &Mutex<String>) -> std::io::Result<()> {
+    // Only the request line matters; scrapers send small requests
+    let mut request = [0u8; 1024];
+    let read = stream.read(&mut request).await?;
+    let request_line = String::from_utf8_lossy(&request[..read]);
+
+    let response = if request_line.starts_with("GET /metrics ") {
+        let body = body.lock().map(|body| body.clone()).unwrap_or_default();
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )
+    } else {
+        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+    };
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_render_prometheus_text() {
+        let providers = HashMap::from([(
+            "lyrics.ovh",
+            ProviderStats {
+                requests: 3,
+                failures: 1,
+                slow_requests: 0,
+                total_latency: Duration::from_millis(1500),
+            },
+        )]);
+        let output = render(&MetricsInput {
+            polls: PollStats { polls: 12, failures: 2 },
+            spotify: SchedulerStats::default(),
+            cache: CacheStats { hits: 4, misses: 3 },
+            providers: &providers,
+        });
+
+        assert!(output.contains(
+            "# HELP lyricsify_polls_total Spotify playback polls\n\
+             # TYPE lyricsify_polls_total counter\n\
+             lyricsify_polls_total 12\n"
+        ));
+        assert!(output.contains("lyricsify_cache_hits_total 4\n"));
+        assert!(output.contains("lyricsify_provider_failures_total{provider=\"lyrics.ovh\"} 1\n"));
+        assert!(output
+            .contains("lyricsify_provider_latency_seconds_total{provider=\"lyrics.ovh\"} 1.5\n"));
+    }
+}
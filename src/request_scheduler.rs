@@ -0,0 +1,219 @@
+use rspotify::http::HttpError;
+use rspotify::ClientError;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Maximum number of Spotify requests that may be issued back to back
+///
+/// Spotify doesn't publish exact limits (they apply over a rolling 30 second
+/// window), so the bucket is sized well below where 429s start appearing.
+const BUCKET_CAPACITY: f64 = 10.0;
+
+/// Sustained Spotify request rate once the burst allowance is used up
+const REFILL_PER_SEC: f64 = 2.0;
+
+/// Back-off used when Spotify returns 429 without a usable Retry-After
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(5);
+
+/// Counters describing how the scheduler has treated requests
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SchedulerStats {
+    /// Requests allowed through
+    pub granted: u64,
+    /// Requests that had to wait for a token or a Retry-After to pass
+    pub delayed: u64,
+    /// 429 responses reported back by callers
+    pub rate_limited: u64,
+}
+
+/// Token bucket state, separated from the clock so it can be tested
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+    /// No requests may be issued before this point (set from Retry-After)
+    blocked_until: Option<Instant>,
+    stats: SchedulerStats,
+}
+
+impl Bucket {
+    fn new(now: Instant) -> Self {
+        Self {
+            tokens: BUCKET_CAPACITY,
+            last_refill: now,
+            blocked_until: None,
+            stats: SchedulerStats::default(),
+        }
+    }
+
+    /// Take a token, or return how long to wait before trying again
+    fn try_take(&mut self, now: Instant) -> Result<(), Duration> {
+        if let Some(blocked_until) = self.blocked_until {
+            if now < blocked_until {
+                return Err(blocked_until - now);
+            }
+            self.blocked_until = None;
+        }
+
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * REFILL_PER_SEC).min(BUCKET_CAPACITY);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            self.stats.granted += 1;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / REFILL_PER_SEC))
+        }
+    }
+
+    /// Hold off every request until `retry_after` has passed
+    fn block_for(&mut self, now: Instant, retry_after: Duration) {
+        let until = now + retry_after;
+        self.blocked_until = Some(self.blocked_until.map_or(until, |current| current.max(until)));
+        self.stats.rate_limited += 1;
+    }
+}
+
+/// Rate limiter shared by every Spotify API call
+///
+/// Every call acquires a token before reaching rspotify, which smooths out
+/// bursts from polling, queue lookups and token refreshes. A 429 reported via
+/// `record_error` pauses all callers until Spotify's Retry-After has passed,
+/// not just the one that hit it.
+#[derive(Debug)]
+pub struct RequestScheduler {
+    bucket: Mutex<Bucket>,
+}
+
+impl RequestScheduler {
+    /// Create a scheduler with a full bucket
+    pub fn new() -> Self {
+        Self {
+            bucket: Mutex::new(Bucket::new(Instant::now())),
+        }
+    }
+
+    /// Wait until a request may be issued
+    pub async fn acquire(&self) {
+        let mut delayed = false;
+        loop {
+            let wait = {
+                let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+                match bucket.try_take(Instant::now()) {
+                    Ok(()) => {
+                        if delayed {
+                            bucket.stats.delayed += 1;
+                        }
+                        return;
+                    }
+                    Err(wait) => wait,
+                }
+            };
+
+            if !delayed {
+                log::debug!("Spotify request delayed {} ms by rate limiter", wait.as_millis());
+                delayed = true;
+            }
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Inspect a failed request and back off globally if it was rate limited
+    pub fn record_error(&self, error: &ClientError) {
+        let Some(retry_after) = rate_limit_retry_after(error) else {
+            return;
+        };
+
+        log::warn!(
+            "Spotify rate limit hit, pausing requests for {}s",
+            retry_after.as_secs()
+        );
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        bucket.block_for(Instant::now(), retry_after);
+    }
+
+    /// Snapshot of the scheduler counters
+    pub fn stats(&self) -> SchedulerStats {
+        self.bucket.lock().unwrap_or_else(|e| e.into_inner()).stats
+    }
+}
+
+impl Default for RequestScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How long to back off for a 429 response, or `None` for any other error
+fn rate_limit_retry_after(error: &ClientError) -> Option<Duration> {
+    let ClientError::Http(http_error) = error else {
+        return None;
+    };
+    let HttpError::StatusCode(response) = http_error.as_ref() else {
+        return None;
+    };
+    if response.status().as_u16() != 429 {
+        return None;
+    }
+
+    let retry_after = response
+        .headers()
+        .get("retry-after")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_RETRY_AFTER);
+    Some(retry_after)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_up_to_capacity_is_allowed() {
+        let now = Instant::now();
+        let mut bucket = Bucket::new(now);
+
+        for _ in 0..BUCKET_CAPACITY as usize {
+            assert!(bucket.try_take(now).is_ok());
+        }
+        let wait = bucket.try_take(now).unwrap_err();
+        assert_eq!(wait, Duration::from_secs_f64(1.0 / REFILL_PER_SEC));
+        assert_eq!(bucket.stats.granted, BUCKET_CAPACITY as u64);
+    }
+
+    #[test]
+    fn test_tokens_refill_over_time() {
+        let now = Instant::now();
+        let mut bucket = Bucket::new(now);
+        while bucket.try_take(now).is_ok() {}
+
+        let later = now + Duration::from_secs_f64(1.0 / REFILL_PER_SEC);
+        assert!(bucket.try_take(later).is_ok());
+        assert!(bucket.try_take(later).is_err());
+    }
+
+    #[test]
+    fn test_retry_after_blocks_even_with_tokens() {
+        let now = Instant::now();
+        let mut bucket = Bucket::new(now);
+
+        bucket.block_for(now, Duration::from_secs(3));
+        assert_eq!(bucket.try_take(now), Err(Duration::from_secs(3)));
+        assert!(bucket.try_take(now + Duration::from_secs(3)).is_ok());
+        assert_eq!(bucket.stats.rate_limited, 1);
+    }
+
+    #[test]
+    fn test_shorter_retry_after_does_not_shrink_block() {
+        let now = Instant::now();
+        let mut bucket = Bucket::new(now);
+
+        bucket.block_for(now, Duration::from_secs(10));
+        bucket.block_for(now, Duration::from_secs(2));
+        assert_eq!(bucket.try_take(now), Err(Duration::from_secs(10)));
+    }
+}
@@ -0,0 +1,134 @@
+use crate::config::AppConfig;
+use crate::error::Result;
+use crate::last_track::LastTrack;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Set when the app starts shutting down, so background tasks stop sending
+/// events before the main channel's receiver goes away
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownFlag(Arc<AtomicBool>);
+
+impl ShutdownFlag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tell every holder of this flag that the app is shutting down
+    pub fn trigger(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// One thing saved to disk on a clean exit
+pub struct FlushStep<'a> {
+    name: &'static str,
+    save: Box<dyn FnOnce() -> Result<()> + 'a>,
+}
+
+impl<'a> FlushStep<'a> {
+    pub fn new(name: &'static str, save: impl FnOnce() -> Result<()> + 'a) -> Self {
+        Self {
+            name,
+            save: Box::new(save),
+        }
+    }
+}
+
+/// The steps that write the app's own files to the config directory `dir`
+///
+/// The app's copy of the configuration comes first, then the last track if
+/// one was playing.
+pub fn config_dir_steps<'a>(
+    dir: &'a Path,
+    config: &'a AppConfig,
+    last_track: Option<&'a LastTrack>,
+) -> Vec<FlushStep<'a>> {
+    let mut steps = vec![FlushStep::new("configuration", move || config.save_in(dir))];
+    if let Some(last_track) = last_track {
+        steps.push(FlushStep::new("last track", move || last_track.save_in(dir)));
+    }
+    steps
+}
+
+/// Run every step in order, returning the names of those that failed
+///
+/// A failing step is logged and the rest still run, so one unwritable file
+/// doesn't lose everything else on the way out.
+pub fn flush_all(steps: Vec<FlushStep>) -> Vec<&'static str> {
+    let mut failed = Vec::new();
+    for step in steps {
+        match (step.save)() {
+            Ok(()) => log::info!("Saved {}", step.name),
+            Err(e) => {
+                log::error!("Failed to save {} on shutdown: {}", step.name, e);
+                failed.push(step.name);
+            }
+        }
+    }
+    failed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::LyricsifyError;
+    use std::fs;
+
+    #[test]
+    fn test_failing_step_does_not_skip_the_rest() {
+        let dir =
+            std::env::temp_dir().join(format!("lyricsify-shutdown-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let config = dir.join("config.json");
+        let last_track = dir.join("last_track.json");
+
+        let failed = flush_all(vec![
+            FlushStep::new("configuration", || Ok(fs::write(&config, "{}")?)),
+            FlushStep::new("widget state", || {
+                Err(LyricsifyError::ConfigError("container missing".to_string()))
+            }),
+            FlushStep::new("last track", || Ok(fs::write(&last_track, "{}")?)),
+        ]);
+
+        assert_eq!(failed, vec!["widget state"]);
+        assert!(config.exists());
+        assert!(last_track.exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_config_dir_steps_write_config_and_last_track() {
+        let dir =
+            std::env::temp_dir().join(format!("lyricsify-steps-test-{}", std::process::id()));
+        let config = AppConfig {
+            theme: "dark".to_string(),
+            ..AppConfig::default()
+        };
+        let last_track = LastTrack {
+            track_id: "spotify:track:a".to_string(),
+            name: "Talisman".to_string(),
+            artists: vec!["Air".to_string()],
+            lyrics: Some("La la la".to_string()),
+        };
+
+        let failed = flush_all(config_dir_steps(&dir, &config, Some(&last_track)));
+
+        assert!(failed.is_empty());
+        let saved: AppConfig =
+            serde_json::from_str(&fs::read_to_string(dir.join("config.json")).unwrap()).unwrap();
+        assert_eq!(saved, config);
+        let saved: LastTrack =
+            serde_json::from_str(&fs::read_to_string(dir.join("last_track.json")).unwrap())
+                .unwrap();
+        assert_eq!(saved.lyrics, last_track.lyrics);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,18 +1,38 @@
 use crate::error::LyricsifyError;
 use crate::app_core::AppEvent;
+use crate::config::{AppConfig, TokenStore, TrackComparison};
+use crate::log_privacy::logged;
+use crate::request_scheduler::{RequestScheduler, SchedulerStats};
+use crate::shutdown::ShutdownFlag;
+use crate::ui_manager::show_notification;
 use rspotify::{
-    clients::OAuthClient,
-    model::PlayableItem,
-    AuthCodeSpotify, Config, Credentials, OAuth, Token,
+    clients::{BaseClient, OAuthClient},
+    model::{
+        CurrentlyPlayingContext, CurrentlyPlayingType, PlayableItem, PlaylistId,
+        SubscriptionLevel, Type,
+    },
+    AuthCodeSpotify, ClientError, Config, Credentials, OAuth, Token,
 };
+use rspotify::http::HttpError;
 use rspotify::scopes;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::{Mutex, mpsc};
-use tokio::time::{interval, Duration};
+use tokio::sync::{Mutex, Notify, mpsc};
+use std::collections::hash_map::RandomState;
+use std::collections::{HashSet, VecDeque};
+use std::hash::BuildHasher;
+use tokio::task::JoinHandle;
+use tokio::time::{Duration, Instant};
 use keyring::Entry;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
-// Notification support will be added in menu bar implementation
+
+/// Whether an account with this subscription may control playback
+fn can_control_playback(product: Option<SubscriptionLevel>) -> bool {
+    product != Some(SubscriptionLevel::Free)
+}
 
 /// Information about a Spotify track
 #[derive(Debug, Clone, PartialEq)]
@@ -21,6 +41,8 @@ pub struct TrackInfo {
     pub name: String,
     pub artists: Vec<String>,
     pub duration_ms: u64,
+    pub album: String,
+    pub release_year: Option<u32>,
 }
 
 impl TrackInfo {
@@ -31,10 +53,256 @@ impl TrackInfo {
             name: track.name.clone(),
             artists: track.artists.iter().map(|a| a.name.clone()).collect(),
             duration_ms: track.duration.num_milliseconds() as u64,
+            album: track.album.name.clone(),
+            release_year: track.album.release_date.as_deref().and_then(parse_release_year),
+        }
+    }
+
+    /// Whether `other` is the same track under `comparison`
+    ///
+    /// Local files have no Spotify id, so those are always compared in full.
+    pub fn same_identity(&self, other: &TrackInfo, comparison: TrackComparison) -> bool {
+        match comparison {
+            TrackComparison::Id if !self.id.is_empty() => self.id == other.id,
+            _ => self == other,
+        }
+    }
+
+    /// Render the text shown in place of lyrics when no provider has any
+    ///
+    /// `{title}`, `{artist}`, `{album}` and `{year}` in `template` are replaced
+    /// with the track's details. Lines whose placeholders all come out empty
+    /// (e.g. `{year}` for an album without a release date) are dropped.
+    pub fn fallback_text(&self, template: &str) -> String {
+        let year = self.release_year.map(|y| y.to_string()).unwrap_or_default();
+        let artists = self.artists.join(", ");
+        let fields = [
+            ("{title}", self.name.as_str()),
+            ("{artist}", artists.as_str()),
+            ("{album}", self.album.as_str()),
+            ("{year}", year.as_str()),
+        ];
+
+        template
+            .lines()
+            .filter_map(|line| {
+                let placeholders: Vec<&str> = fields
+                    .iter()
+                    .filter(|(placeholder, _)| line.contains(placeholder))
+                    .map(|(_, value)| *value)
+                    .collect();
+                if !placeholders.is_empty() && placeholders.iter().all(|value| value.is_empty()) {
+                    return None;
+                }
+
+                Some(fields.iter().fold(line.to_string(), |line, (placeholder, value)| {
+                    line.replace(placeholder, value)
+                }))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Extract the year from a Spotify release date ("1998", "1998-05" or "1998-05-01")
+///
+/// Spotify reports "0000" for some releases with an unknown date.
+fn parse_release_year(release_date: &str) -> Option<u32> {
+    release_date
+        .get(..4)?
+        .parse()
+        .ok()
+        .filter(|&year| year > 0)
+}
+
+/// A Spotify Connect device that can play music
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceInfo {
+    pub id: String,
+    pub name: String,
+    pub is_active: bool,
+}
+
+/// Playback position of the current track as reported by Spotify
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaybackProgress {
+    pub progress_ms: u64,
+    pub duration_ms: u64,
+    pub is_playing: bool,
+}
+
+/// What Spotify reported as currently playing
+#[derive(Debug, Clone, PartialEq)]
+pub enum NowPlaying {
+    /// A track and its playback position
+    Track(TrackInfo, PlaybackProgress),
+    /// Playing, but without an item: an ad break, or a moment between tracks
+    NoItem { advertisement: bool },
+    /// Nothing playing, or something without lyrics (podcast episodes)
+    Stopped,
+}
+
+impl NowPlaying {
+    fn from_context(playing: Option<CurrentlyPlayingContext>) -> Self {
+        let Some(playing) = playing else {
+            return NowPlaying::Stopped;
+        };
+        match playing.item {
+            Some(PlayableItem::Track(track)) => {
+                let track_info = TrackInfo::from_full_track(&track);
+                let progress = PlaybackProgress {
+                    progress_ms: playing
+                        .progress
+                        .map(|p| p.num_milliseconds().max(0) as u64)
+                        .unwrap_or(0),
+                    duration_ms: track_info.duration_ms,
+                    is_playing: playing.is_playing,
+                };
+                NowPlaying::Track(track_info, progress)
+            }
+            // We don't support podcasts for lyrics
+            Some(PlayableItem::Episode(_)) => NowPlaying::Stopped,
+            None if playing.is_playing => {
+                let advertisement =
+                    playing.currently_playing_type == CurrentlyPlayingType::Advertisement;
+                NowPlaying::NoItem { advertisement }
+            }
+            None => NowPlaying::Stopped,
+        }
+    }
+}
+
+/// What a poll reported, coarse enough that a repeated report compares equal
+///
+/// The play state is part of the key so a pause or resume within the same
+/// second is never mistaken for a duplicate; a seek lands in another bucket.
+#[derive(Debug, Clone, PartialEq)]
+struct PollKey {
+    track_id: String,
+    progress_secs: u64,
+    is_playing: bool,
+}
+
+impl PollKey {
+    fn new(track: &TrackInfo, progress: &PlaybackProgress) -> Self {
+        Self {
+            track_id: track.id.clone(),
+            progress_secs: progress.progress_ms / 1000,
+            is_playing: progress.is_playing,
         }
     }
 }
 
+/// How close to the start/end of a track a position must be to count as a loop
+const LOOP_BOUNDARY_WINDOW_MS: u64 = 15_000;
+
+impl PlaybackProgress {
+    /// Time left until the track ends while playing, `None` while paused
+    pub fn remaining(&self) -> Option<Duration> {
+        self.is_playing
+            .then(|| Duration::from_millis(self.duration_ms.saturating_sub(self.progress_ms)))
+    }
+
+    /// Whether this report shows the same track starting over after `previous`
+    ///
+    /// With repeat-one enabled Spotify reports the same track on every poll,
+    /// so a loop only shows up as the position jumping from near the end back
+    /// to near the start.
+    pub fn is_loop_restart(&self, previous: &PlaybackProgress) -> bool {
+        let previous_remaining = previous.duration_ms.saturating_sub(previous.progress_ms);
+
+        previous_remaining <= LOOP_BOUNDARY_WINDOW_MS
+            && self.progress_ms <= LOOP_BOUNDARY_WINDOW_MS
+            && self.progress_ms < previous.progress_ms
+    }
+}
+
+/// Counters for the playback poll loop
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PollStats {
+    /// Polls attempted (retries within a poll count once)
+    pub polls: u64,
+    /// Polls that failed after all retries
+    pub failures: u64,
+}
+
+/// Poll counters shared with the background poll task
+#[derive(Debug, Default)]
+struct PollCounters {
+    polls: AtomicU64,
+    failures: AtomicU64,
+}
+
+/// Spotify polling interval
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Largest share of the poll interval each tick may be moved by
+const POLL_JITTER_FRACTION: f64 = 0.1;
+
+/// `base` moved by a random amount of up to `fraction` of it in either direction
+///
+/// Keeps instances started at the same moment (e.g. on login) from polling in
+/// lockstep. The randomness comes from the std hasher's per-instance keys, which
+/// is plenty for spreading timers and avoids a dependency.
+fn jittered(base: Duration, fraction: f64) -> Duration {
+    let random = RandomState::new().hash_one(Instant::now());
+    // Map to [-1.0, 1.0]
+    let unit = (random as f64 / u64::MAX as f64) * 2.0 - 1.0;
+    base.mul_f64(1.0 + unit * fraction)
+}
+
+/// Restarts of a crashed poll loop allowed within `POLLER_RESTART_WINDOW`
+/// before giving up
+const MAX_POLLER_RESTARTS: usize = 5;
+
+/// Span over which poll loop restarts count as rapid failures
+const POLLER_RESTART_WINDOW: Duration = Duration::from_secs(300);
+
+/// Delay before the first restart of a crashed poll loop, doubled on each
+/// further restart within the window
+const POLLER_RESTART_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Tracks recent poll loop crashes to pace restarts and stop a crash loop
+#[derive(Debug)]
+struct RestartBudget {
+    recent: VecDeque<Instant>,
+}
+
+impl RestartBudget {
+    fn new() -> Self {
+        Self {
+            recent: VecDeque::new(),
+        }
+    }
+
+    /// Record a crash at `now`, returning how long to wait before restarting,
+    /// or `None` once there have been too many crashes in the window
+    fn record_crash(&mut self, now: Instant) -> Option<Duration> {
+        while self
+            .recent
+            .front()
+            .is_some_and(|&crash| now.duration_since(crash) > POLLER_RESTART_WINDOW)
+        {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(now);
+
+        let crashes = self.recent.len();
+        (crashes <= MAX_POLLER_RESTARTS)
+            .then(|| POLLER_RESTART_BASE_DELAY * 2u32.pow(crashes as u32 - 1))
+    }
+}
+
+/// Aborts the task when dropped, so a cancelled supervisor takes its poll
+/// loop down with it
+struct AbortOnDrop(JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
 /// Serializable token data for keychain storage
 #[derive(Debug, Serialize, Deserialize)]
 struct StoredToken {
@@ -42,43 +310,331 @@ struct StoredToken {
     refresh_token: Option<String>,
     expires_at: Option<DateTime<Utc>>,
     scopes: Vec<String>,
+    /// Redirect URI the token was obtained with (absent in older entries)
+    #[serde(default)]
+    redirect_uri: Option<String>,
+}
+
+impl StoredToken {
+    /// Store `token`, with its scopes sorted so the saved JSON doesn't depend
+    /// on `HashSet` iteration order
+    fn new(token: Token, redirect_uri: String) -> Self {
+        let mut scopes: Vec<String> = token.scopes.into_iter().collect();
+        scopes.sort();
+
+        Self {
+            access_token: token.access_token,
+            refresh_token: token.refresh_token,
+            expires_at: token.expires_at,
+            scopes,
+            redirect_uri: Some(redirect_uri),
+        }
+    }
+
+    /// Scopes in `required` that the token wasn't granted, compared as sets
+    fn missing_scopes(&self, required: &HashSet<String>) -> Vec<String> {
+        let granted: HashSet<&String> = self.scopes.iter().collect();
+        let mut missing: Vec<String> = required
+            .iter()
+            .filter(|scope| !granted.contains(scope))
+            .cloned()
+            .collect();
+        missing.sort();
+        missing
+    }
+
+    /// Whether the token was obtained with a different redirect URI than `current`
+    ///
+    /// Tokens saved before the URI was recorded are assumed to match.
+    fn redirect_uri_differs(&self, current: &str) -> bool {
+        self.redirect_uri.as_deref().is_some_and(|uri| uri != current)
+    }
+}
+
+/// Longest an access token can plausibly have left: Spotify issues them for
+/// an hour, plus slack for small clock differences
+const MAX_TOKEN_LIFETIME_SECS: i64 = 2 * 60 * 60;
+
+/// Whether a token expiring at `expires_at` can still be used at `now`
+///
+/// It must expire more than 60 seconds from now. An expiry further ahead than
+/// any token lives means the clock was wrong when it was saved, so the token
+/// is refreshed rather than trusted.
+fn expiry_is_valid(expires_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+    if expires_at > now + chrono::Duration::seconds(MAX_TOKEN_LIFETIME_SECS) {
+        log::warn!(
+            "Token expiry {} is implausibly far ahead of the clock ({}), probably clock \
+             skew; refreshing instead",
+            expires_at,
+            now
+        );
+        return false;
+    }
+    expires_at > now + chrono::Duration::seconds(60)
 }
 
 const KEYCHAIN_SERVICE: &str = "com.lyricsify.spotify";
 const KEYCHAIN_ACCOUNT: &str = "spotify_token";
+/// Token file in the config directory, used when the keychain is unavailable
+const TOKEN_FILE_NAME: &str = "spotify_token.json";
+
+/// How many times a keychain operation is attempted on transient failures
+const KEYCHAIN_RETRY_ATTEMPTS: u32 = 3;
+/// Delay between keychain attempts
+const KEYCHAIN_RETRY_DELAY: Duration = Duration::from_millis(500);
+/// `errSecInteractionNotAllowed`, returned while the login keychain is locked
+const ERR_SEC_INTERACTION_NOT_ALLOWED: &str = "-25308";
+
+/// Where the Spotify token is persisted
+#[derive(Debug, Clone, PartialEq)]
+enum TokenBackend {
+    /// The macOS keychain
+    Keychain,
+    /// A JSON file readable only by the current user, for systems (e.g. CI)
+    /// without a usable keychain. Not encrypted: the file permissions are
+    /// the only protection.
+    File(PathBuf),
+}
+
+impl TokenBackend {
+    /// Pick the backend for the configured token store
+    ///
+    /// `Auto` uses the keychain unless probing it fails outright; transient
+    /// lock-screen errors still count as available.
+    fn select(store: TokenStore) -> Self {
+        let use_keychain = match store {
+            TokenStore::Keychain => true,
+            TokenStore::File => false,
+            TokenStore::Auto => keychain_available(),
+        };
+
+        if use_keychain {
+            return TokenBackend::Keychain;
+        }
+        match AppConfig::config_dir() {
+            Ok(dir) => {
+                let path = dir.join(TOKEN_FILE_NAME);
+                log::warn!("Storing the Spotify token in {:?} instead of the keychain", path);
+                TokenBackend::File(path)
+            }
+            Err(e) => {
+                log::error!("No location for the token file ({}), using the keychain", e);
+                TokenBackend::Keychain
+            }
+        }
+    }
+
+    /// Name used in log messages
+    fn name(&self) -> &'static str {
+        match self {
+            TokenBackend::Keychain => "keychain",
+            TokenBackend::File(_) => "token file",
+        }
+    }
+}
+
+/// Whether the keychain can be used at all
+fn keychain_available() -> bool {
+    match Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT).and_then(|entry| entry.get_password()) {
+        Ok(_) | Err(keyring::Error::NoEntry) => true,
+        Err(e) if is_transient_keyring_error(&e) => true,
+        Err(e) => {
+            log::warn!("Keychain unavailable: {}", e);
+            false
+        }
+    }
+}
+
+/// Send an event from the poll loop, returning false if it should stop
+///
+/// The receiver going away during shutdown is expected, so that isn't logged.
+async fn send_poll_event(
+    event_tx: &mpsc::Sender<AppEvent>,
+    shutdown: &ShutdownFlag,
+    event: AppEvent,
+) -> bool {
+    if shutdown.is_triggered() {
+        return false;
+    }
+    match event_tx.send(event).await {
+        Ok(()) => true,
+        Err(e) => {
+            if !shutdown.is_triggered() {
+                log::error!("Failed to send poll event: {}", e);
+            }
+            false
+        }
+    }
+}
+
+/// Write the token JSON to `path`, readable and writable only by the user
+fn write_token_file(path: &Path, json: &str) -> Result<(), LyricsifyError> {
+    use std::io::Write;
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    // `mode` only applies on creation, so tighten a pre-existing file too
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    file.write_all(json.as_bytes())?;
+    Ok(())
+}
 
-/// Display a macOS notification
-/// 
-/// This function uses the native NSUserNotificationCenter API to display
-/// a notification to the user.
-fn show_notification(title: &str, message: &str) {
-    // For now, just log the notification
-    // Full notification support will be added when implementing menu bar
-    log::info!("Notification: {} - {}", title, message);
+/// Whether a failed refresh means Spotify rejected the refresh token itself
+///
+/// Spotify answers a revoked or expired refresh token with 400
+/// `invalid_grant` (or 401); anything else is treated as transient.
+fn is_token_rejected(error: &ClientError) -> bool {
+    let ClientError::Http(http_error) = error else {
+        return false;
+    };
+    matches!(
+        http_error.as_ref(),
+        HttpError::StatusCode(response) if matches!(response.status().as_u16(), 400 | 401)
+    )
+}
+
+/// Whether a keychain error is transient and worth retrying
+///
+/// During login and screen-lock transitions the keychain rejects access with
+/// `errSecInteractionNotAllowed` even though the item exists. keyring reports
+/// this as a generic platform failure, so it is recognised by its status code
+/// or message.
+fn is_transient_keyring_error(error: &keyring::Error) -> bool {
+    match error {
+        keyring::Error::PlatformFailure(inner) | keyring::Error::NoStorageAccess(inner) => {
+            let debug = format!("{:?}", inner);
+            let message = inner.to_string().to_lowercase();
+            debug.contains(ERR_SEC_INTERACTION_NOT_ALLOWED)
+                || message.contains("interaction is not allowed")
+        }
+        _ => false,
+    }
+}
+
+/// Run a keychain operation, retrying briefly on transient failures
+async fn with_keychain_retry<T>(
+    operation: &str,
+    mut f: impl FnMut() -> keyring::Result<T>,
+) -> keyring::Result<T> {
+    let mut attempt = 1;
+    loop {
+        match f() {
+            Err(e) if attempt < KEYCHAIN_RETRY_ATTEMPTS && is_transient_keyring_error(&e) => {
+                log::warn!(
+                    "Keychain {} failed transiently (attempt {}/{}): {}",
+                    operation,
+                    attempt,
+                    KEYCHAIN_RETRY_ATTEMPTS,
+                    e
+                );
+                tokio::time::sleep(KEYCHAIN_RETRY_DELAY).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Read a credential from its configured command, falling back to the environment
+///
+/// The command is run through `sh -c` and its trimmed stdout is used as the
+/// value. If the command fails or prints nothing, the environment variable
+/// `env_var` is used instead.
+fn read_credential(env_var: &str, command: Option<&str>) -> Result<String, LyricsifyError> {
+    if let Some(command) = command {
+        match run_credential_command(command) {
+            Ok(value) => {
+                log::info!("Read {} from credential command", env_var);
+                return Ok(value);
+            }
+            Err(e) => {
+                log::warn!("Credential command for {} failed ({}), falling back", env_var, e);
+            }
+        }
+    }
+
+    std::env::var(env_var)
+        .map_err(|_| LyricsifyError::AuthenticationFailed(
+            format!("{} environment variable not set", env_var)
+        ))
+}
+
+/// Run a credential command and return its trimmed output
+fn run_credential_command(command: &str) -> Result<String, String> {
+    let output = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .output()
+        .map_err(|e| format!("failed to run command: {}", e))?;
+
+    if !output.status.success() {
+        // Don't include stdout/stderr in the error, they may contain the secret
+        return Err(format!("command exited with {}", output.status));
+    }
+
+    let value = String::from_utf8(output.stdout)
+        .map_err(|_| "command output is not valid UTF-8".to_string())?
+        .trim()
+        .to_string();
+
+    if value.is_empty() {
+        return Err("command printed nothing".to_string());
+    }
+
+    Ok(value)
 }
 
 /// Manages Spotify authentication and API interactions
 pub struct SpotifyClient {
     client: Arc<AuthCodeSpotify>,
     current_track: Arc<Mutex<Option<TrackInfo>>>,
+    /// Every API call acquires from this before reaching rspotify
+    scheduler: Arc<RequestScheduler>,
+    /// Where the token is persisted between runs
+    token_backend: TokenBackend,
+    /// Serializes token store access, so concurrent saves can't interleave
+    /// and a read never sees a half-finished write
+    token_store_lock: Arc<Mutex<()>>,
+    /// Wakes the poll loop for an immediate poll
+    poll_trigger: Arc<Notify>,
+    poll_counters: Arc<PollCounters>,
+    /// Supervisor of the running poll loop, if polling has started
+    poller: Option<JoinHandle<()>>,
+    /// Expiry of the token as last saved to or loaded from the token store,
+    /// to notice when rspotify has refreshed the token in memory
+    stored_token_expiry: Arc<Mutex<Option<DateTime<Utc>>>>,
+    /// What the poller counts as a track change
+    track_comparison: TrackComparison,
+    /// The account's subscription, once known; `None` when it couldn't be read
+    account_product: Option<SubscriptionLevel>,
 }
 
 impl SpotifyClient {
     /// Create a new SpotifyClient with OAuth2 configuration
     /// 
     /// This initializes the client with the required scopes for reading
-    /// currently playing track information.
-    pub fn new() -> Result<Self, LyricsifyError> {
-        // Get credentials from environment variables
-        let client_id = std::env::var("SPOTIFY_CLIENT_ID")
-            .map_err(|_| LyricsifyError::AuthenticationFailed(
-                "SPOTIFY_CLIENT_ID environment variable not set".to_string()
-            ))?;
-        
-        let client_secret = std::env::var("SPOTIFY_CLIENT_SECRET")
-            .map_err(|_| LyricsifyError::AuthenticationFailed(
-                "SPOTIFY_CLIENT_SECRET environment variable not set".to_string()
-            ))?;
+    /// currently playing track information. Credentials come from the
+    /// configured credential commands when set, falling back to environment
+    /// variables.
+    pub fn new(app_config: &AppConfig) -> Result<Self, LyricsifyError> {
+        let client_id = read_credential(
+            "SPOTIFY_CLIENT_ID",
+            app_config.client_id_command.as_deref(),
+        )?;
+
+        let client_secret = read_credential(
+            "SPOTIFY_CLIENT_SECRET",
+            app_config.client_secret_command.as_deref(),
+        )?;
 
         let redirect_uri = std::env::var("SPOTIFY_REDIRECT_URI")
             .unwrap_or_else(|_| "http://localhost:8888/callback".to_string());
@@ -91,7 +647,12 @@ impl SpotifyClient {
             redirect_uri,
             scopes: scopes!(
                 "user-read-currently-playing",
-                "user-read-playback-state"
+                "user-read-playback-state",
+                "user-modify-playback-state",
+                // Only for the account's subscription level
+                "user-read-private",
+                // Playlist cache warming; public playlists don't need it
+                "playlist-read-private"
             ),
             ..Default::default()
         };
@@ -108,6 +669,15 @@ impl SpotifyClient {
         Ok(Self {
             client: Arc::new(client),
             current_track: Arc::new(Mutex::new(None)),
+            scheduler: Arc::new(RequestScheduler::new()),
+            token_backend: TokenBackend::select(app_config.token_store),
+            token_store_lock: Arc::new(Mutex::new(())),
+            poll_trigger: Arc::new(Notify::new()),
+            poll_counters: Arc::new(PollCounters::default()),
+            poller: None,
+            stored_token_expiry: Arc::new(Mutex::new(None)),
+            track_comparison: app_config.track_comparison,
+            account_product: None,
         })
     }
 
@@ -158,36 +728,252 @@ impl SpotifyClient {
         Ok(token)
     }
 
+    /// Save the token if rspotify has refreshed it since it was last stored
+    ///
+    /// With `token_refreshing` on, rspotify swaps in a new token when a
+    /// request finds the old one expired, but only in memory. Without this a
+    /// token refreshed while polling would be lost on restart.
+    pub async fn save_token_if_refreshed(&self) -> Result<(), LyricsifyError> {
+        let current = Self::token_expiry(&self.client).await;
+        if current.is_none() || current == *self.stored_token_expiry.lock().await {
+            return Ok(());
+        }
+
+        log::info!("Token was refreshed in the background, saving it");
+        self.save_token().await
+    }
+
+    /// Expiry of the client's in-memory token, if it has one
+    async fn token_expiry(client: &AuthCodeSpotify) -> Option<DateTime<Utc>> {
+        client.token.lock().await.ok()?.as_ref()?.expires_at
+    }
+
     /// Check if the client is currently authenticated
     pub async fn is_authenticated(&self) -> bool {
         self.client.token.lock().await.unwrap().is_some()
     }
 
     /// Get the currently playing track from Spotify
-    pub async fn get_current_track(&self) -> Result<Option<TrackInfo>, LyricsifyError> {
+    pub async fn get_current_track(&self) -> Result<NowPlaying, LyricsifyError> {
+        self.scheduler.acquire().await;
         let currently_playing = self.client
             .current_playing(None, None::<Vec<_>>)
             .await
-            .map_err(|e| LyricsifyError::SpotifyApiError(
-                format!("Failed to get currently playing track: {}", e)
-            ))?;
+            .map_err(|e| {
+                self.scheduler.record_error(&e);
+                LyricsifyError::SpotifyApiError(
+                    format!("Failed to get currently playing track: {}", e)
+                )
+            })?;
 
-        if let Some(playing) = currently_playing {
-            if let Some(item) = playing.item {
-                match item {
-                    PlayableItem::Track(track) => {
-                        let track_info = TrackInfo::from_full_track(&track);
-                        return Ok(Some(track_info));
-                    }
-                    PlayableItem::Episode(_) => {
-                        // We don't support podcasts for lyrics
-                        return Ok(None);
-                    }
-                }
+        Ok(NowPlaying::from_context(currently_playing))
+    }
+
+    /// Get the upcoming tracks from the user's playback queue
+    ///
+    /// Uses the queue endpoint, which is covered by the
+    /// `user-read-currently-playing` and `user-read-playback-state` scopes.
+    /// Episodes in the queue are skipped since we don't fetch lyrics for them.
+    /// The returned future doesn't borrow the client, so it can be spawned.
+    pub fn get_queue(
+        &self,
+    ) -> impl Future<Output = Result<Vec<TrackInfo>, LyricsifyError>> + Send + 'static {
+        let client = Arc::clone(&self.client);
+        let scheduler = Arc::clone(&self.scheduler);
+        async move {
+            scheduler.acquire().await;
+            let queue = client
+                .current_user_queue()
+                .await
+                .map_err(|e| {
+                    scheduler.record_error(&e);
+                    LyricsifyError::SpotifyApiError(
+                        format!("Failed to get playback queue: {}", e)
+                    )
+                })?;
+
+            let tracks = queue
+                .queue
+                .iter()
+                .filter_map(|item| match item {
+                    PlayableItem::Track(track) => Some(TrackInfo::from_full_track(track)),
+                    PlayableItem::Episode(_) => None,
+                })
+                .collect();
+
+            Ok(tracks)
+        }
+    }
+
+    /// Spotify URI of the playlist playback is coming from, if any
+    pub async fn current_playlist_uri(&self) -> Result<Option<String>, LyricsifyError> {
+        self.scheduler.acquire().await;
+        let currently_playing = self.client
+            .current_playing(None, None::<Vec<_>>)
+            .await
+            .map_err(|e| {
+                self.scheduler.record_error(&e);
+                LyricsifyError::SpotifyApiError(
+                    format!("Failed to get playback context: {}", e)
+                )
+            })?;
+
+        Ok(currently_playing
+            .and_then(|playing| playing.context)
+            .filter(|context| context._type == Type::Playlist)
+            .map(|context| context.uri))
+    }
+
+    /// Spotify Connect devices available to the user
+    ///
+    /// Devices Spotify reports without an ID can't be controlled and are left out.
+    pub async fn list_devices(&self) -> Result<Vec<DeviceInfo>, LyricsifyError> {
+        self.scheduler.acquire().await;
+        let devices = self.client.device().await.map_err(|e| {
+            self.scheduler.record_error(&e);
+            LyricsifyError::SpotifyApiError(format!("Failed to list devices: {}", e))
+        })?;
+
+        Ok(devices
+            .into_iter()
+            .filter_map(|device| {
+                Some(DeviceInfo {
+                    id: device.id?,
+                    name: device.name,
+                    is_active: device.is_active,
+                })
+            })
+            .collect())
+    }
+
+    /// Move playback to a device and start playing there
+    ///
+    /// Tokens obtained before the playback-control scope was requested are
+    /// refused by Spotify; re-authenticating grants it.
+    pub async fn transfer_playback(&self, device_id: &str) -> Result<(), LyricsifyError> {
+        self.scheduler.acquire().await;
+        self.client
+            .transfer_playback(device_id, Some(true))
+            .await
+            .map_err(|e| {
+                self.scheduler.record_error(&e);
+                LyricsifyError::SpotifyApiError(format!("Failed to transfer playback: {}", e))
+            })
+    }
+
+    /// Get up to `limit` tracks of a playlist, in playlist order
+    ///
+    /// Pages through the playlist 100 items at a time, each page going
+    /// through the rate limiter. Episodes and unavailable items are skipped.
+    /// Private playlists need the `playlist-read-private` scope, which tokens
+    /// from before it was requested lack until the user authenticates again.
+    pub async fn get_context_tracks(
+        &self,
+        playlist_uri: &str,
+        limit: usize,
+    ) -> Result<Vec<TrackInfo>, LyricsifyError> {
+        const PAGE_SIZE: u32 = 100;
+
+        let playlist_id = PlaylistId::from_uri(playlist_uri).map_err(|e| {
+            LyricsifyError::SpotifyApiError(format!("Invalid playlist URI {}: {}", playlist_uri, e))
+        })?;
+
+        let mut tracks = Vec::new();
+        let mut offset = 0;
+        while tracks.len() < limit {
+            self.scheduler.acquire().await;
+            let page = self.client
+                .playlist_items_manual(
+                    playlist_id.as_ref(),
+                    None,
+                    None,
+                    Some(PAGE_SIZE),
+                    Some(offset),
+                )
+                .await
+                .map_err(|e| {
+                    self.scheduler.record_error(&e);
+                    LyricsifyError::SpotifyApiError(
+                        format!("Failed to get playlist tracks: {}", e)
+                    )
+                })?;
+
+            tracks.extend(page.items.iter().filter_map(|item| match &item.track {
+                Some(PlayableItem::Track(track)) => Some(TrackInfo::from_full_track(track)),
+                _ => None,
+            }));
+
+            if page.next.is_none() {
+                break;
             }
+            offset += PAGE_SIZE;
         }
 
-        Ok(None)
+        tracks.truncate(limit);
+        Ok(tracks)
+    }
+
+    /// Whether a client ID and secret were configured
+    pub fn has_credentials(&self) -> bool {
+        !self.client.creds.id.is_empty()
+            && self.client.creds.secret.as_deref().is_some_and(|s| !s.is_empty())
+    }
+
+    /// Check that the token store can be read (a missing token still counts as accessible)
+    pub async fn check_token_store(&self) -> Result<(), LyricsifyError> {
+        self.read_token_json().await.map(|_| ())
+    }
+
+    /// Make a lightweight authenticated call to check the Spotify API is reachable
+    pub async fn check_api_reachable(&self) -> Result<(), LyricsifyError> {
+        self.scheduler.acquire().await;
+        self.client.current_user().await.map(|_| ()).map_err(|e| {
+            self.scheduler.record_error(&e);
+            LyricsifyError::SpotifyApiError(format!("Failed to reach Spotify: {}", e))
+        })
+    }
+
+    /// Look up whether the account is Premium, remembering the result
+    ///
+    /// Spotify only reports the subscription to tokens with the
+    /// `user-read-private` scope; without it, or when the lookup fails, the
+    /// product stays unknown and nothing is restricted.
+    pub async fn refresh_account_product(&mut self) -> Option<SubscriptionLevel> {
+        self.scheduler.acquire().await;
+        self.account_product = match self.client.current_user().await {
+            Ok(user) => user.product,
+            Err(e) => {
+                self.scheduler.record_error(&e);
+                log::warn!("Failed to look up the Spotify account type: {}", e);
+                None
+            }
+        };
+        match self.account_product {
+            Some(product) => log::info!("Spotify account type: {:?}", product),
+            None => log::info!("Spotify account type unknown"),
+        }
+        self.account_product
+    }
+
+    /// Whether playback controls (such as switching devices) can work
+    ///
+    /// Spotify refuses them for free accounts. An unknown account type counts
+    /// as Premium, so a missing scope never disables anything.
+    pub fn can_control_playback(&self) -> bool {
+        can_control_playback(self.account_product)
+    }
+
+    /// Counters from the shared Spotify rate limiter
+    pub fn scheduler_stats(&self) -> SchedulerStats {
+        self.scheduler.stats()
+    }
+
+    /// Counters from the playback poll loop
+    pub fn poll_stats(&self) -> PollStats {
+        PollStats {
+            polls: self.poll_counters.polls.load(Ordering::Relaxed),
+            failures: self.poll_counters.failures.load(Ordering::Relaxed),
+        }
     }
 
     /// Get a reference to the internal client for advanced operations
@@ -195,27 +981,32 @@ impl SpotifyClient {
         Arc::clone(&self.client)
     }
 
-    /// Save the current token to the macOS keychain
+    /// Save the current token to the token store
     /// 
-    /// This securely stores the access token, refresh token, and expiry
-    /// information in the system keychain for persistence across app restarts.
-    pub async fn save_token_to_keychain(&self) -> Result<(), LyricsifyError> {
+    /// This stores the access token, refresh token, and expiry information in
+    /// the system keychain (or the token file when the keychain is unavailable)
+    /// for persistence across app restarts.
+    pub async fn save_token(&self) -> Result<(), LyricsifyError> {
+        // The token is read under the lock, so whichever save runs last
+        // writes the newest token rather than the one it started with
+        let _store = self.token_store_lock.lock().await;
         let token = self.get_token().await?;
         
         if let Some(token) = token {
-            let stored_token = StoredToken {
-                access_token: token.access_token,
-                refresh_token: token.refresh_token,
-                expires_at: token.expires_at,
-                scopes: token.scopes.into_iter().collect(),
-            };
+            let stored_token = StoredToken::new(token, self.client.oauth.redirect_uri.clone());
 
             let json = serde_json::to_string(&stored_token)?;
             
-            let entry = Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT)?;
-            entry.set_password(&json)?;
+            match &self.token_backend {
+                TokenBackend::Keychain => {
+                    let entry = Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT)?;
+                    with_keychain_retry("write", || entry.set_password(&json)).await?;
+                }
+                TokenBackend::File(path) => write_token_file(path, &json)?,
+            }
+            *self.stored_token_expiry.lock().await = stored_token.expires_at;
             
-            log::info!("Token saved to keychain successfully");
+            log::info!("Token saved to {} successfully", self.token_backend.name());
             Ok(())
         } else {
             Err(LyricsifyError::AuthenticationFailed(
@@ -224,36 +1015,75 @@ impl SpotifyClient {
         }
     }
 
-    /// Load token from the macOS keychain
+    /// Load token from the token store
     /// 
-    /// Retrieves the stored token from the keychain and sets it in the client.
+    /// Retrieves the stored token and sets it in the client.
     /// Returns true if a valid token was loaded, false if no token exists.
-    pub async fn load_token_from_keychain(&self) -> Result<bool, LyricsifyError> {
-        let entry = Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT)?;
-        
-        match entry.get_password() {
-            Ok(json) => {
-                let stored_token: StoredToken = serde_json::from_str(&json)?;
-                
-                // Convert back to rspotify Token
-                let token = Token {
-                    access_token: stored_token.access_token,
-                    refresh_token: stored_token.refresh_token,
-                    expires_at: stored_token.expires_at,
-                    scopes: stored_token.scopes.into_iter().collect(),
-                    expires_in: chrono::Duration::zero(), // Not used when expires_at is set
-                };
-
-                self.set_token(token).await?;
-                log::info!("Token loaded from keychain successfully");
+    pub async fn load_token(&self) -> Result<bool, LyricsifyError> {
+        match self.read_stored_token().await? {
+            Some(stored_token) => {
+                self.apply_stored_token(stored_token).await?;
                 Ok(true)
             }
-            Err(keyring::Error::NoEntry) => {
-                log::info!("No token found in keychain");
-                Ok(false)
+            None => Ok(false),
+        }
+    }
+
+    /// Read the stored token without applying it
+    async fn read_stored_token(&self) -> Result<Option<StoredToken>, LyricsifyError> {
+        match self.read_token_json().await? {
+            Some(json) => Ok(Some(serde_json::from_str(&json)?)),
+            None => {
+                log::info!("No token found in {}", self.token_backend.name());
+                Ok(None)
+            }
+        }
+    }
+
+    /// Read the raw token JSON from the active backend, `None` if nothing is stored
+    async fn read_token_json(&self) -> Result<Option<String>, LyricsifyError> {
+        let _store = self.token_store_lock.lock().await;
+        match &self.token_backend {
+            TokenBackend::Keychain => {
+                let entry = Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT)?;
+                match with_keychain_retry("read", || entry.get_password()).await {
+                    Ok(json) => Ok(Some(json)),
+                    Err(keyring::Error::NoEntry) => Ok(None),
+                    Err(e) => Err(LyricsifyError::KeyringError(e)),
+                }
             }
-            Err(e) => Err(LyricsifyError::KeyringError(e)),
+            TokenBackend::File(path) => match std::fs::read_to_string(path) {
+                Ok(json) => Ok(Some(json)),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(LyricsifyError::IoError(e)),
+            },
+        }
+    }
+
+    /// Set a token read from the token store in the client
+    async fn apply_stored_token(&self, stored_token: StoredToken) -> Result<(), LyricsifyError> {
+        *self.stored_token_expiry.lock().await = stored_token.expires_at;
+
+        let missing = stored_token.missing_scopes(&self.client.oauth.scopes);
+        if !missing.is_empty() {
+            log::warn!(
+                "Stored token lacks scopes {}; authenticate again if Spotify requests fail",
+                missing.join(", ")
+            );
         }
+
+        // Convert back to rspotify Token
+        let token = Token {
+            access_token: stored_token.access_token,
+            refresh_token: stored_token.refresh_token,
+            expires_at: stored_token.expires_at,
+            scopes: stored_token.scopes.into_iter().collect(),
+            expires_in: chrono::Duration::zero(), // Not used when expires_at is set
+        };
+
+        self.set_token(token).await?;
+        log::info!("Token loaded from {} successfully", self.token_backend.name());
+        Ok(())
     }
 
     /// Validate that the current token is not expired
@@ -262,39 +1092,46 @@ impl SpotifyClient {
     pub async fn is_token_valid(&self) -> bool {
         if let Ok(Some(token)) = self.get_token().await {
             if let Some(expires_at) = token.expires_at {
-                // Consider token valid if it expires more than 60 seconds from now
-                let now = Utc::now();
-                let buffer = chrono::Duration::seconds(60);
-                return expires_at > now + buffer;
+                return expiry_is_valid(expires_at, Utc::now());
             }
         }
         false
     }
 
-    /// Clear the token from the keychain
+    /// Clear the token from the token store
     /// 
     /// Useful for logout or when re-authentication is required.
-    pub fn clear_token_from_keychain(&self) -> Result<(), LyricsifyError> {
-        let entry = Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT)?;
-        
-        match entry.delete_credential() {
-            Ok(_) => {
-                log::info!("Token cleared from keychain");
-                Ok(())
-            }
-            Err(keyring::Error::NoEntry) => {
-                // Already cleared, not an error
-                Ok(())
+    pub async fn clear_token(&self) -> Result<(), LyricsifyError> {
+        let _store = self.token_store_lock.lock().await;
+        let result = match &self.token_backend {
+            TokenBackend::Keychain => {
+                let entry = Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT)?;
+                match entry.delete_credential() {
+                    // Already cleared, not an error
+                    Ok(_) | Err(keyring::Error::NoEntry) => Ok(()),
+                    Err(e) => Err(LyricsifyError::KeyringError(e)),
+                }
             }
-            Err(e) => Err(LyricsifyError::KeyringError(e)),
+            TokenBackend::File(path) => match std::fs::remove_file(path) {
+                Ok(()) => Ok(()),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                Err(e) => Err(LyricsifyError::IoError(e)),
+            },
+        };
+
+        if result.is_ok() {
+            log::info!("Token cleared from {}", self.token_backend.name());
         }
+        result
     }
 
     /// Refresh the access token using the refresh token
     /// 
-    /// This method attempts to refresh the access token when it's expired or
-    /// about to expire. If the refresh fails, it clears the stored token and
-    /// returns an error indicating re-authentication is needed.
+    /// This method forces a refresh regardless of the token's expiry. If
+    /// Spotify rejects the refresh token, it clears the stored token and
+    /// returns an error indicating re-authentication is needed. Transient
+    /// failures (network errors, 5xx, rate limiting) keep the current token so
+    /// a later attempt can still succeed.
     pub async fn refresh_token(&self) -> Result<(), LyricsifyError> {
         log::info!("Attempting to refresh token");
         
@@ -305,27 +1142,34 @@ impl SpotifyClient {
             ));
         }
 
-        // Attempt to refresh the token
-        // rspotify automatically refreshes tokens when token_refreshing is enabled
-        // We just need to trigger a request that will cause the refresh
-        match self.client.current_user().await {
-            Ok(_) => {
+        self.scheduler.acquire().await;
+        match BaseClient::refresh_token(self.client.as_ref()).await {
+            Ok(()) => {
                 log::info!("Token refreshed successfully");
                 
-                // Save the new token to keychain
-                self.save_token_to_keychain().await?;
+                // Save the new token to the token store
+                self.save_token().await?;
                 Ok(())
             }
-            Err(e) => {
-                log::error!("Token refresh failed: {}", e);
+            Err(e) if is_token_rejected(&e) => {
+                log::error!("Token refresh rejected: {}", e);
                 
-                // Clear the invalid token from keychain
-                let _ = self.clear_token_from_keychain();
+                // The refresh token is no longer valid, so drop it everywhere
+                let _ = self.clear_token().await;
+                *self.client.token.lock().await.unwrap() = None;
                 
                 Err(LyricsifyError::AuthenticationFailed(
                     format!("Token refresh failed, re-authentication required: {}", e)
                 ))
             }
+            Err(e) => {
+                self.scheduler.record_error(&e);
+                log::warn!("Token refresh failed, keeping current token: {}", e);
+
+                Err(LyricsifyError::SpotifyApiError(
+                    format!("Token refresh failed, will retry later: {}", e)
+                ))
+            }
         }
     }
 
@@ -361,13 +1205,30 @@ impl SpotifyClient {
         log::info!("Initializing Spotify client");
         
         // Try to load token from keychain
-        let token_loaded = self.load_token_from_keychain().await?;
-        
-        if !token_loaded {
+        let Some(stored_token) = self.read_stored_token().await? else {
             log::info!("No stored token found, authentication required");
             return Ok(false);
+        };
+
+        // A token from a different redirect URI may still work, but the next
+        // re-authentication would fail against the current OAuth config
+        let redirect_uri = &self.client.oauth.redirect_uri;
+        if stored_token.redirect_uri_differs(redirect_uri) {
+            log::warn!(
+                "Stored token used redirect URI {:?}, now {:?}; re-authentication required",
+                stored_token.redirect_uri.as_deref().unwrap_or_default(),
+                redirect_uri
+            );
+            let _ = self.clear_token().await;
+            show_notification(
+                "Lyricsify",
+                "The Spotify redirect URI changed. Please authenticate again."
+            );
+            return Ok(false);
         }
 
+        self.apply_stored_token(stored_token).await?;
+
         // Check if token is valid
         if self.is_token_valid().await {
             log::info!("Stored token is valid");
@@ -381,6 +1242,11 @@ impl SpotifyClient {
                 log::info!("Token refreshed successfully");
                 Ok(true)
             }
+            // Transient failure: keep the token, rspotify refreshes it on the next request
+            Err(_) if self.is_authenticated().await => {
+                log::warn!("Token refresh failed temporarily, will retry while polling");
+                Ok(true)
+            }
             Err(_) => {
                 log::warn!("Token refresh failed, authentication required");
                 Ok(false)
@@ -395,85 +1261,238 @@ impl SpotifyClient {
     /// TrackChanged event through the provided channel.
     /// 
     /// The polling loop includes error handling with exponential backoff and
-    /// continues running even after errors.
-    pub fn start_polling(&self, event_tx: mpsc::Sender<AppEvent>) {
+    /// continues running even after errors. If the loop itself crashes, it is
+    /// restarted with a growing delay; after `MAX_POLLER_RESTARTS` crashes in
+    /// quick succession a `PollingStopped` event is sent instead. Calling this
+    /// again replaces the running poll loop, which is how the menu's restart
+    /// action recovers. Once `shutdown` is triggered the loop stops quietly.
+    pub fn start_polling(&mut self, event_tx: mpsc::Sender<AppEvent>, shutdown: ShutdownFlag) {
+        if let Some(poller) = self.poller.take() {
+            log::info!("Restarting Spotify track polling");
+            poller.abort();
+        }
+
         let client = Arc::clone(&self.client);
         let current_track = Arc::clone(&self.current_track);
-        
-        tokio::spawn(async move {
-            let mut poll_interval = interval(Duration::from_secs(5));
-            log::info!("Started Spotify track polling (5 second interval)");
-            
+        let scheduler = Arc::clone(&self.scheduler);
+        let poll_trigger = Arc::clone(&self.poll_trigger);
+        let poll_counters = Arc::clone(&self.poll_counters);
+        let stored_token_expiry = Arc::clone(&self.stored_token_expiry);
+        let track_comparison = self.track_comparison;
+
+        self.poller = Some(tokio::spawn(async move {
+            let mut budget = RestartBudget::new();
             loop {
-                poll_interval.tick().await;
-                
-                // Attempt to get current track with retry logic
-                match Self::get_current_track_with_retry(&client).await {
-                    Ok(new_track) => {
-                        // Check if track has changed
-                        let mut current = current_track.lock().await;
-                        
-                        if *current != new_track {
-                            log::info!("Track changed: {:?}", new_track);
-                            
-                            // Update stored track
-                            *current = new_track.clone();
-                            
-                            // Send event if track exists
-                            if let Some(track) = new_track {
-                                if let Err(e) = event_tx.send(AppEvent::TrackChanged(track)).await {
-                                    log::error!("Failed to send TrackChanged event: {}", e);
+                let mut poll_loop = AbortOnDrop(tokio::spawn(Self::poll_loop(
+                    Arc::clone(&client),
+                    Arc::clone(&current_track),
+                    Arc::clone(&scheduler),
+                    Arc::clone(&poll_trigger),
+                    Arc::clone(&poll_counters),
+                    Arc::clone(&stored_token_expiry),
+                    track_comparison,
+                    event_tx.clone(),
+                    shutdown.clone(),
+                )));
+
+                // A clean exit means the app stopped listening
+                let error = match (&mut poll_loop.0).await {
+                    Ok(()) => break,
+                    Err(e) => e,
+                };
+                log::error!("Spotify polling task crashed: {}", error);
+
+                if shutdown.is_triggered() {
+                    break;
+                }
+                let Some(delay) = budget.record_crash(Instant::now()) else {
+                    log::error!(
+                        "Spotify polling crashed {} times in {} seconds, giving up",
+                        budget.recent.len(),
+                        POLLER_RESTART_WINDOW.as_secs()
+                    );
+                    let _ = event_tx.send(AppEvent::PollingStopped).await;
+                    break;
+                };
+                log::warn!(
+                    "Restarting Spotify polling in {} seconds (restart {} of {})",
+                    delay.as_secs(),
+                    budget.recent.len(),
+                    MAX_POLLER_RESTARTS
+                );
+                tokio::time::sleep(delay).await;
+            }
+        }));
+    }
+
+    /// Poll for playback changes until the event channel closes or the app
+    /// shuts down
+    #[allow(clippy::too_many_arguments)]
+    async fn poll_loop(
+        client: Arc<AuthCodeSpotify>,
+        current_track: Arc<Mutex<Option<TrackInfo>>>,
+        scheduler: Arc<RequestScheduler>,
+        poll_trigger: Arc<Notify>,
+        poll_counters: Arc<PollCounters>,
+        stored_token_expiry: Arc<Mutex<Option<DateTime<Utc>>>>,
+        track_comparison: TrackComparison,
+        event_tx: mpsc::Sender<AppEvent>,
+        shutdown: ShutdownFlag,
+    ) {
+        log::info!(
+            "Started Spotify track polling ({} second interval)",
+            POLL_INTERVAL.as_secs()
+        );
+
+        // Each deadline is perturbed on its own rather than drifting, so
+        // the average rate stays at POLL_INTERVAL
+        let mut next_poll = Instant::now();
+        let mut last_poll: Option<PollKey> = None;
+        let mut in_ad_break = false;
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep_until(next_poll) => {}
+                _ = poll_trigger.notified() => {
+                    log::debug!("Polling immediately on request");
+                    next_poll = Instant::now();
+                }
+            }
+            if shutdown.is_triggered() {
+                break;
+            }
+            next_poll += jittered(POLL_INTERVAL, POLL_JITTER_FRACTION);
+            if next_poll < Instant::now() {
+                // A slow poll overran its slot; don't burst to catch up
+                next_poll = Instant::now() + jittered(POLL_INTERVAL, POLL_JITTER_FRACTION);
+            }
+            
+            // Attempt to get current track with retry logic
+            poll_counters.polls.fetch_add(1, Ordering::Relaxed);
+            match Self::get_current_track_with_retry(&client, &scheduler).await {
+                Ok(now_playing) => {
+                    let (new_track, progress) = match now_playing {
+                        NowPlaying::Track(track, progress) => (Some(track), Some(progress)),
+                        NowPlaying::Stopped => (None, None),
+                        NowPlaying::NoItem { advertisement } => {
+                            // Between tracks the last one stays, lyrics and all;
+                            // an ad replaces it until the next track is reported
+                            if advertisement && !in_ad_break {
+                                log::info!("Advertisement playing");
+                                in_ad_break = true;
+                                let event = AppEvent::AdvertisementPlaying;
+                                if !send_poll_event(&event_tx, &shutdown, event).await {
                                     break; // Exit if channel is closed
                                 }
                             }
+                            last_poll = None;
+                            continue;
                         }
-                    }
-                    Err(e) => {
-                        log::error!("Failed to get current track after retries: {}", e);
+                    };
+
+                    // Check if track has changed. After an ad the track is
+                    // reported again even if it didn't change, to replace the
+                    // ad notice.
+                    let mut current = current_track.lock().await;
+                    
+                    let changed = std::mem::take(&mut in_ad_break)
+                        || match (&*current, &new_track) {
+                            (Some(old), Some(new)) => !old.same_identity(new, track_comparison),
+                            (old, new) => old.is_some() != new.is_some(),
+                        };
+                    if changed {
+                        match &new_track {
+                            Some(track) => log::info!(
+                                "Track changed: {} by {}",
+                                logged(&track.name),
+                                logged(&track.artists.join(", "))
+                            ),
+                            None => log::info!("Track changed: nothing playing"),
+                        }
+                        
+                        // Update stored track
+                        *current = new_track.clone();
                         
-                        // Send error event
-                        if let Err(send_err) = event_tx.send(AppEvent::SpotifyError(e.to_string())).await {
-                            log::error!("Failed to send SpotifyError event: {}", send_err);
+                        // Report the new track, or that playback stopped
+                        let event = match new_track {
+                            Some(track) => AppEvent::TrackChanged(track),
+                            None => AppEvent::PlaybackStopped,
+                        };
+                        if !send_poll_event(&event_tx, &shutdown, event).await {
+                            break; // Exit if channel is closed
+                        }
+                    }
+                    let key = current
+                        .as_ref()
+                        .zip(progress.as_ref())
+                        .map(|(track, progress)| PollKey::new(track, progress));
+                    drop(current);
+
+                    // Report the playback position, unless Spotify just repeated
+                    // the last report
+                    if key.is_some() && key == last_poll {
+                        log::trace!("Skipping duplicate poll result");
+                    } else if let Some(progress) = progress {
+                        let event = AppEvent::PlaybackProgress(progress);
+                        if !send_poll_event(&event_tx, &shutdown, event).await {
+                            break; // Exit if channel is closed
+                        }
+                    }
+                    last_poll = key;
+
+                    // The poll may have made rspotify refresh the token
+                    let expiry = Self::token_expiry(&client).await;
+                    if expiry.is_some() && expiry != *stored_token_expiry.lock().await {
+                        if !send_poll_event(&event_tx, &shutdown, AppEvent::TokenRefreshed).await {
                             break; // Exit if channel is closed
                         }
                     }
                 }
+                Err(e) => {
+                    log::error!("Failed to get current track after retries: {}", e);
+                    poll_counters.failures.fetch_add(1, Ordering::Relaxed);
+                    
+                    // Send error event
+                    let event = AppEvent::SpotifyError(e.to_string());
+                    if !send_poll_event(&event_tx, &shutdown, event).await {
+                        break; // Exit if channel is closed
+                    }
+                }
             }
-            
+        }
+
+        if shutdown.is_triggered() {
+            log::info!("Spotify polling stopped for shutdown");
+        } else {
             log::warn!("Spotify polling loop terminated");
-        });
+        }
+    }
+
+    /// Make the poll loop poll now instead of at its next tick
+    ///
+    /// Has no effect until `start_polling` has been called. Requests made
+    /// while a poll is running cause one more poll right after it.
+    pub fn poll_now(&self) {
+        self.poll_trigger.notify_one();
     }
 
     /// Get current track with exponential backoff retry logic
     /// 
     /// Attempts to fetch the current track up to 3 times with delays of 1s, 2s, 4s
-    /// between attempts. Returns the track info or an error if all attempts fail.
+    /// between attempts. Returns the track info and playback progress, or an
+    /// error if all attempts fail.
     async fn get_current_track_with_retry(
         client: &AuthCodeSpotify,
-    ) -> Result<Option<TrackInfo>, LyricsifyError> {
+        scheduler: &RequestScheduler,
+    ) -> Result<NowPlaying, LyricsifyError> {
         let retry_delays = [1, 2, 4]; // Exponential backoff: 1s, 2s, 4s
         let mut last_error = None;
         
         for (attempt, &delay_secs) in retry_delays.iter().enumerate() {
+            scheduler.acquire().await;
             match client.current_playing(None, None::<Vec<_>>).await {
-                Ok(currently_playing) => {
-                    if let Some(playing) = currently_playing {
-                        if let Some(item) = playing.item {
-                            match item {
-                                PlayableItem::Track(track) => {
-                                    let track_info = TrackInfo::from_full_track(&track);
-                                    return Ok(Some(track_info));
-                                }
-                                PlayableItem::Episode(_) => {
-                                    // We don't support podcasts for lyrics
-                                    return Ok(None);
-                                }
-                            }
-                        }
-                    }
-                    return Ok(None);
-                }
+                Ok(currently_playing) => return Ok(NowPlaying::from_context(currently_playing)),
                 Err(e) => {
+                    scheduler.record_error(&e);
                     log::warn!(
                         "Attempt {} failed to get current track: {}",
                         attempt + 1,
@@ -496,3 +1515,296 @@ impl SpotifyClient {
         )))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(progress_ms: u64) -> PlaybackProgress {
+        PlaybackProgress {
+            progress_ms,
+            duration_ms: 200_000,
+            is_playing: true,
+        }
+    }
+
+    #[test]
+    fn test_loop_restart_detected_at_wrap_around() {
+        assert!(progress(2_000).is_loop_restart(&progress(197_000)));
+        // Wrapping exactly at the end also counts
+        assert!(progress(0).is_loop_restart(&progress(200_000)));
+    }
+
+    #[test]
+    fn test_normal_playback_is_not_a_loop_restart() {
+        assert!(!progress(105_000).is_loop_restart(&progress(100_000)));
+        assert!(!progress(199_000).is_loop_restart(&progress(195_000)));
+    }
+
+    #[test]
+    fn test_seek_back_from_middle_is_not_a_loop_restart() {
+        assert!(!progress(1_000).is_loop_restart(&progress(100_000)));
+    }
+
+    #[test]
+    fn test_remaining_only_while_playing() {
+        assert_eq!(progress(150_000).remaining(), Some(Duration::from_secs(50)));
+        // Spotify can report a position just past the duration
+        assert_eq!(progress(201_000).remaining(), Some(Duration::ZERO));
+
+        let paused = PlaybackProgress {
+            is_playing: false,
+            ..progress(150_000)
+        };
+        assert_eq!(paused.remaining(), None);
+    }
+
+    #[test]
+    fn test_same_identity_by_id_or_metadata() {
+        let renamed = TrackInfo {
+            name: "Windowlicker (Remastered)".to_string(),
+            ..track()
+        };
+        assert!(track().same_identity(&renamed, TrackComparison::Id));
+        assert!(!track().same_identity(&renamed, TrackComparison::Metadata));
+        assert!(track().same_identity(&track(), TrackComparison::Metadata));
+
+        let other = TrackInfo {
+            id: "spotify:track:def".to_string(),
+            ..track()
+        };
+        assert!(!track().same_identity(&other, TrackComparison::Id));
+        assert!(!track().same_identity(&other, TrackComparison::Metadata));
+
+        // Local files have no id to go by
+        let local = |name: &str| TrackInfo {
+            id: String::new(),
+            name: name.to_string(),
+            ..track()
+        };
+        assert!(!local("Flim").same_identity(&local("Xtal"), TrackComparison::Id));
+        assert!(local("Flim").same_identity(&local("Flim"), TrackComparison::Id));
+    }
+
+    #[test]
+    fn test_poll_key_ignores_repeats_but_not_pauses() {
+        let track = track();
+        let key = PollKey::new(&track, &progress(10_200));
+
+        assert_eq!(key, PollKey::new(&track, &progress(10_900)));
+        assert_ne!(key, PollKey::new(&track, &progress(11_000)));
+
+        let paused = PlaybackProgress {
+            is_playing: false,
+            ..progress(10_200)
+        };
+        assert_ne!(key, PollKey::new(&track, &paused));
+    }
+
+    #[test]
+    fn test_jitter_stays_within_fraction() {
+        let base = Duration::from_secs(5);
+        for _ in 0..100 {
+            let jittered = jittered(base, 0.1);
+            assert!(jittered >= Duration::from_millis(4_500), "{:?}", jittered);
+            assert!(jittered <= Duration::from_millis(5_500), "{:?}", jittered);
+        }
+    }
+
+    #[test]
+    fn test_poller_restart_backoff_and_limit() {
+        let start = Instant::now();
+        let mut budget = RestartBudget::new();
+
+        let delays: Vec<_> = (0..MAX_POLLER_RESTARTS as u64)
+            .map(|i| budget.record_crash(start + Duration::from_secs(i)))
+            .collect();
+        assert_eq!(delays[0], Some(Duration::from_secs(1)));
+        assert_eq!(delays[1], Some(Duration::from_secs(2)));
+        assert_eq!(delays[4], Some(Duration::from_secs(16)));
+
+        // One crash too many in the window gives up
+        assert_eq!(budget.record_crash(start + Duration::from_secs(10)), None);
+
+        // Crashes outside the window no longer count
+        let later = start + POLLER_RESTART_WINDOW + Duration::from_secs(60);
+        assert_eq!(budget.record_crash(later), Some(Duration::from_secs(1)));
+    }
+
+    fn track() -> TrackInfo {
+        TrackInfo {
+            id: "spotify:track:abc".to_string(),
+            name: "Windowlicker".to_string(),
+            artists: vec!["Aphex Twin".to_string()],
+            duration_ms: 367_000,
+            album: "Windowlicker".to_string(),
+            release_year: Some(1999),
+        }
+    }
+
+    #[test]
+    fn test_parse_release_year_handles_all_precisions() {
+        assert_eq!(parse_release_year("1999"), Some(1999));
+        assert_eq!(parse_release_year("1999-03"), Some(1999));
+        assert_eq!(parse_release_year("1999-03-22"), Some(1999));
+        assert_eq!(parse_release_year("0000"), None);
+        assert_eq!(parse_release_year(""), None);
+    }
+
+    #[test]
+    fn test_fallback_text_fills_placeholders() {
+        let text = track().fallback_text("{title}\n{artist}\n{album} ({year})");
+        assert_eq!(text, "Windowlicker\nAphex Twin\nWindowlicker (1999)");
+    }
+
+    #[test]
+    fn test_fallback_text_drops_lines_with_only_empty_fields() {
+        let track = TrackInfo {
+            release_year: None,
+            ..track()
+        };
+        let text = track.fallback_text("{title}\n{year}\n\nLyrics not available");
+        assert_eq!(text, "Windowlicker\n\nLyrics not available");
+    }
+
+    fn stored_token(redirect_uri: Option<&str>) -> StoredToken {
+        StoredToken {
+            access_token: "access".to_string(),
+            refresh_token: None,
+            expires_at: None,
+            scopes: Vec::new(),
+            redirect_uri: redirect_uri.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_redirect_uri_change_is_detected() {
+        let token = stored_token(Some("http://localhost:8888/callback"));
+        assert!(!token.redirect_uri_differs("http://localhost:8888/callback"));
+        assert!(token.redirect_uri_differs("http://127.0.0.1:9000/callback"));
+    }
+
+    #[test]
+    fn test_skewed_token_expiry_is_not_trusted() {
+        let now = Utc::now();
+        let minutes = chrono::Duration::minutes;
+
+        assert!(expiry_is_valid(now + minutes(59), now));
+        assert!(!expiry_is_valid(now + chrono::Duration::seconds(30), now));
+        assert!(!expiry_is_valid(now - minutes(5), now));
+
+        // Saved with the clock a day behind, or read with it a year ahead
+        assert!(!expiry_is_valid(now + chrono::Duration::days(1), now));
+        assert!(!expiry_is_valid(now - chrono::Duration::days(365), now));
+    }
+
+    #[test]
+    fn test_only_free_accounts_lose_playback_controls() {
+        assert!(can_control_playback(Some(SubscriptionLevel::Premium)));
+        assert!(!can_control_playback(Some(SubscriptionLevel::Free)));
+        // Tokens without user-read-private don't report the subscription
+        assert!(can_control_playback(None));
+    }
+
+    #[test]
+    fn test_stored_scopes_are_sorted_and_compared_as_sets() {
+        let scopes = [
+            "user-read-playback-state",
+            "user-modify-playback-state",
+            "user-read-currently-playing",
+        ];
+        let token = Token {
+            access_token: "access".to_string(),
+            expires_in: chrono::Duration::zero(),
+            expires_at: None,
+            refresh_token: None,
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+        };
+
+        let stored = StoredToken::new(token, "http://localhost:8888/callback".to_string());
+        assert_eq!(
+            stored.scopes,
+            vec![
+                "user-modify-playback-state",
+                "user-read-currently-playing",
+                "user-read-playback-state",
+            ]
+        );
+
+        let mut required: HashSet<String> = scopes.iter().rev().map(|s| s.to_string()).collect();
+        assert!(stored.missing_scopes(&required).is_empty());
+
+        required.insert("user-read-private".to_string());
+        assert_eq!(stored.missing_scopes(&required), vec!["user-read-private"]);
+    }
+
+    #[test]
+    fn test_token_without_redirect_uri_is_accepted() {
+        let json = r#"{"access_token":"a","refresh_token":null,"expires_at":null,"scopes":[]}"#;
+        let token: StoredToken = serde_json::from_str(json).unwrap();
+
+        assert_eq!(token.redirect_uri, None);
+        assert!(!token.redirect_uri_differs("http://127.0.0.1:9000/callback"));
+        assert!(!stored_token(None).redirect_uri_differs("http://localhost:8888/callback"));
+    }
+
+    fn platform_failure(message: &str) -> keyring::Error {
+        keyring::Error::PlatformFailure(Box::new(std::io::Error::other(message.to_string())))
+    }
+
+    #[test]
+    fn test_interaction_not_allowed_is_transient() {
+        assert!(is_transient_keyring_error(&platform_failure("User interaction is not allowed.")));
+        assert!(is_transient_keyring_error(&platform_failure("OSStatus -25308")));
+    }
+
+    #[test]
+    fn test_other_keyring_errors_are_not_transient() {
+        assert!(!is_transient_keyring_error(&keyring::Error::NoEntry));
+        assert!(!is_transient_keyring_error(&platform_failure(
+            "The specified item could not be found in the keychain."
+        )));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_token_saves_leave_latest_token_stored() {
+        // `Auto` would probe the real keychain while picking a backend
+        let config = AppConfig {
+            client_id_command: Some("echo test-id".to_string()),
+            client_secret_command: Some("echo test-secret".to_string()),
+            token_store: TokenStore::File,
+            ..AppConfig::default()
+        };
+        let mut client = SpotifyClient::new(&config).unwrap();
+        // Stored in a temp dir rather than the config directory
+        let dir = std::env::temp_dir().join(format!("lyricsify-token-test-{}", std::process::id()));
+        client.token_backend = TokenBackend::File(dir.join(TOKEN_FILE_NAME));
+        let client = Arc::new(client);
+
+        // Each task plays the role of a refresh followed by a save
+        let tasks: Vec<_> = (0..32)
+            .map(|i| {
+                let client = Arc::clone(&client);
+                tokio::spawn(async move {
+                    let token = Token {
+                        access_token: format!("access-{}", i),
+                        refresh_token: Some(format!("refresh-{}", i)),
+                        ..Token::default()
+                    };
+                    client.set_token(token).await.unwrap();
+                    client.save_token().await.unwrap();
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let stored = client.read_stored_token().await.unwrap().unwrap();
+        let current = client.get_token().await.unwrap().unwrap();
+        assert_eq!(stored.access_token, current.access_token);
+        assert_eq!(stored.refresh_token, current.refresh_token);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
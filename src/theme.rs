@@ -0,0 +1,202 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::AppConfig;
+use crate::error::Result;
+
+/// Name of the theme used when none is configured or the chosen one is broken
+pub const DEFAULT_THEME_NAME: &str = "default";
+
+/// Blur material behind the overlay text
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeMaterial {
+    /// Dark heads-up-display blur
+    #[default]
+    Hud,
+    /// Light or dark depending on the system appearance
+    Popover,
+    /// Matches the menu bar menus
+    Menu,
+    /// Subtle blur used by window backgrounds
+    UnderWindow,
+}
+
+/// Appearance of the overlay in one shareable place
+///
+/// Themes are JSON files in the `themes` directory of the config dir, e.g.
+/// `themes/solarized.json`, selected with the `theme` config option. Fields
+/// missing from a file keep their default values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    /// Lyrics color as `#RRGGBB` or `#RRGGBBAA`
+    pub text_color: String,
+    /// Font family name; the system font when unset or not installed
+    pub font_name: Option<String>,
+    /// Font size in points
+    pub font_size: f64,
+    /// Extra space between lines in points
+    pub line_spacing: f64,
+    /// Space between the window edge and the text in points
+    pub padding: f64,
+    /// Opacity of the whole overlay, from 0.1 to 1.0
+    pub window_alpha: f64,
+    /// Blur material behind the text
+    pub material: ThemeMaterial,
+    /// Draw the window shadow
+    pub shadow: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            text_color: "#FFFFFF".to_string(),
+            font_name: None,
+            font_size: 14.0,
+            line_spacing: 0.0,
+            padding: 20.0,
+            window_alpha: 0.8,
+            material: ThemeMaterial::Hud,
+            shadow: true,
+        }
+    }
+}
+
+impl Theme {
+    /// Look up a theme shipped with the app
+    pub fn built_in(name: &str) -> Option<Self> {
+        match name {
+            DEFAULT_THEME_NAME => Some(Self::default()),
+            "large" => Some(Self {
+                font_size: 20.0,
+                line_spacing: 4.0,
+                padding: 24.0,
+                window_alpha: 0.9,
+                ..Self::default()
+            }),
+            "light" => Some(Self {
+                text_color: "#1C1C1E".to_string(),
+                material: ThemeMaterial::Popover,
+                window_alpha: 0.95,
+                ..Self::default()
+            }),
+            _ => None,
+        }
+    }
+
+    /// Load the theme called `name`, built-in or from the themes directory
+    ///
+    /// An unknown name or unreadable file falls back to the default theme, and
+    /// out-of-range values are replaced, so a bad theme never stops the app.
+    pub fn load(name: &str) -> Self {
+        if let Some(theme) = Self::built_in(name) {
+            return theme;
+        }
+
+        let theme = Self::file_path(name).and_then(|path| {
+            let contents = fs::read_to_string(&path)?;
+            log::info!("Loaded theme from {:?}", path);
+            Ok(serde_json::from_str::<Theme>(&contents)?)
+        });
+        match theme {
+            Ok(theme) => theme.validated(),
+            Err(e) => {
+                log::warn!("Failed to load theme {:?} ({}), using default", name, e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Get the path of a theme file in the config directory
+    fn file_path(name: &str) -> Result<PathBuf> {
+        Ok(AppConfig::config_dir()?
+            .join("themes")
+            .join(format!("{}.json", name)))
+    }
+
+    /// Replace values that can't be displayed with the defaults
+    fn validated(mut self) -> Self {
+        let default = Self::default();
+
+        if parse_hex_color(&self.text_color).is_none() {
+            log::warn!("Invalid theme text_color {:?}, using default", self.text_color);
+            self.text_color = default.text_color;
+        }
+        if !(6.0..=96.0).contains(&self.font_size) {
+            log::warn!("Theme font_size {} out of range, using default", self.font_size);
+            self.font_size = default.font_size;
+        }
+        if !(0.0..=48.0).contains(&self.line_spacing) {
+            self.line_spacing = default.line_spacing;
+        }
+        if !(0.0..=100.0).contains(&self.padding) {
+            self.padding = default.padding;
+        }
+        if !(0.1..=1.0).contains(&self.window_alpha) {
+            log::warn!("Theme window_alpha {} out of range, using default", self.window_alpha);
+            self.window_alpha = default.window_alpha;
+        }
+        self
+    }
+
+    /// Text color components (red, green, blue, alpha) from 0.0 to 1.0
+    pub fn text_rgba(&self) -> (f64, f64, f64, f64) {
+        parse_hex_color(&self.text_color).unwrap_or((1.0, 1.0, 1.0, 1.0))
+    }
+}
+
+/// Parse `#RRGGBB` or `#RRGGBBAA` into components from 0.0 to 1.0
+fn parse_hex_color(color: &str) -> Option<(f64, f64, f64, f64)> {
+    let hex = color.strip_prefix('#')?;
+    if !matches!(hex.len(), 6 | 8) || !hex.is_ascii() {
+        return None;
+    }
+
+    let component = |i: usize| {
+        u8::from_str_radix(&hex[i..i + 2], 16)
+            .ok()
+            .map(|value| f64::from(value) / 255.0)
+    };
+    let alpha = if hex.len() == 8 { component(6)? } else { 1.0 };
+    Some((component(0)?, component(2)?, component(4)?, alpha))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(parse_hex_color("#FFFFFF"), Some((1.0, 1.0, 1.0, 1.0)));
+        assert_eq!(parse_hex_color("#00000000"), Some((0.0, 0.0, 0.0, 0.0)));
+        assert_eq!(parse_hex_color("FFFFFF"), None);
+        assert_eq!(parse_hex_color("#FFF"), None);
+        assert_eq!(parse_hex_color("#GGGGGG"), None);
+    }
+
+    #[test]
+    fn test_built_in_themes_are_valid() {
+        for name in [DEFAULT_THEME_NAME, "large", "light"] {
+            let theme = Theme::built_in(name).unwrap();
+            assert_eq!(theme.clone().validated(), theme, "{}", name);
+        }
+        assert!(Theme::built_in("nonexistent").is_none());
+    }
+
+    #[test]
+    fn test_partial_theme_file_keeps_defaults_and_fixes_bad_values() {
+        let json = r##"{ "text_color": "#FFCC00", "font_size": 500, "material": "menu" }"##;
+        let theme = serde_json::from_str::<Theme>(json).unwrap().validated();
+
+        assert_eq!(
+            theme,
+            Theme {
+                text_color: "#FFCC00".to_string(),
+                material: ThemeMaterial::Menu,
+                ..Theme::default()
+            }
+        );
+    }
+}
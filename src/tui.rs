@@ -0,0 +1,332 @@
+use std::io::{self, Write};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::interval;
+
+use crate::app_core::AppEvent;
+use crate::config::AppConfig;
+use crate::error::Result;
+use crate::log_privacy;
+use crate::lyrics_fetcher::{LyricsFetcher, LyricsResult, SyncedLyrics};
+use crate::shutdown::ShutdownFlag;
+use crate::spotify_client::{PlaybackProgress, SpotifyClient, TrackInfo};
+
+/// Command-line flag that shows lyrics in the terminal instead of the overlay
+pub const TUI_FLAG: &str = "--tui";
+
+/// Clear the screen and move to the top-left corner
+const CLEAR: &str = "\x1b[2J\x1b[H";
+/// Move to the start of the position line and clear it
+const POSITION_LINE: &str = "\x1b[2;1H\x1b[2K";
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// How often the current line of synced lyrics is checked between polls
+const LINE_CHECK_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Lyrics as shown under the track
+enum Shown {
+    /// Plain lyrics, or why there are none
+    Text(String),
+    /// Synced lyrics and the line highlighted as current, if any
+    Synced(SyncedLyrics, Option<usize>),
+}
+
+/// Show the playing track and its lyrics in the terminal until interrupted
+///
+/// Uses the same Spotify poller and lyrics fetcher as the overlay, but none
+/// of AppKit, so it needs no window server. Signing in still happens from
+/// the menu bar app, whose stored token this picks up.
+pub async fn run() -> Result<()> {
+    let config = AppConfig::load()?;
+    log_privacy::set_redacted(config.redact_track_info);
+
+    let mut spotify_client = SpotifyClient::new(&config)?;
+    let lyrics_fetcher = Arc::new(LyricsFetcher::new(&config));
+
+    if !spotify_client.initialize().await? {
+        println!("Not signed in to Spotify. Authenticate from the Lyricsify menu bar app first.");
+        return Ok(());
+    }
+
+    let (event_tx, mut event_rx) = mpsc::channel(100);
+    let shutdown = ShutdownFlag::new();
+    spotify_client.start_polling(event_tx, shutdown.clone());
+
+    let mut interrupt = signal(SignalKind::interrupt())?;
+    let mut terminate = signal(SignalKind::terminate())?;
+
+    // Fetches run in the background so a slow provider never holds up
+    // quitting; results come back tagged with their track's ID
+    let (lyrics_tx, mut lyrics_rx) = mpsc::channel::<(String, Shown)>(1);
+    let mut fetch: Option<JoinHandle<()>> = None;
+
+    let mut track: Option<TrackInfo> = None;
+    let mut shown = Shown::Text(String::new());
+    // Last reported position and when it was received
+    let mut playback: Option<(PlaybackProgress, Instant)> = None;
+    let mut line_check = interval(LINE_CHECK_INTERVAL);
+
+    draw_message("Waiting for Spotify…");
+    loop {
+        tokio::select! {
+            event = event_rx.recv() => {
+                let Some(event) = event else {
+                    break;
+                };
+                match event {
+                    AppEvent::TrackChanged(new_track) => {
+                        if let Some(fetch) = fetch.take() {
+                            fetch.abort();
+                        }
+                        playback = None;
+
+                        if config.skipped_tracks.contains(&new_track.id) {
+                            shown = Shown::Text("No lyrics (skipped)".to_string());
+                        } else {
+                            let loading = new_track.fallback_text(&config.loading_template);
+                            shown = Shown::Text(loading);
+                            fetch = Some(spawn_fetch(
+                                Arc::clone(&lyrics_fetcher),
+                                new_track.clone(),
+                                new_track.fallback_text(&config.no_lyrics_template),
+                                lyrics_tx.clone(),
+                            ));
+                        }
+                        draw_lyrics(&new_track, &shown);
+                        track = Some(new_track);
+                    }
+                    AppEvent::PlaybackProgress(progress) => {
+                        playback = Some((progress, Instant::now()));
+                        if let Some(track) = &track {
+                            update_current_line(track, &mut shown, playback_position(&playback));
+                        }
+                        draw_position(&progress);
+                    }
+                    AppEvent::AdvertisementPlaying => {
+                        stop_track(&mut track, &mut fetch, &mut playback);
+                        draw_message("Advertisement");
+                    }
+                    AppEvent::PlaybackStopped => {
+                        stop_track(&mut track, &mut fetch, &mut playback);
+                        draw_message("Nothing playing");
+                    }
+                    AppEvent::TokenRefreshed => {
+                        if let Err(e) = spotify_client.save_token_if_refreshed().await {
+                            log::warn!("Failed to save refreshed token: {}", e);
+                        }
+                    }
+                    AppEvent::PollingStopped => {
+                        draw_message("Lost connection to Spotify");
+                        break;
+                    }
+                    AppEvent::SpotifyError(error) => log::warn!("Spotify error: {}", error),
+                    // Everything else comes from the overlay or menu bar
+                    _ => {}
+                }
+            }
+            Some((track_id, lyrics)) = lyrics_rx.recv() => {
+                // A fetch for an earlier track may have finished just before
+                // it was aborted
+                let Some(track) = track.as_ref().filter(|track| track.id == track_id) else {
+                    continue;
+                };
+                shown = match lyrics {
+                    Shown::Synced(synced, _) => {
+                        let line = synced.line_at(playback_position(&playback));
+                        Shown::Synced(synced, line)
+                    }
+                    text => text,
+                };
+                draw_lyrics(track, &shown);
+                if let Some((progress, _)) = &playback {
+                    draw_position(progress);
+                }
+            }
+            _ = line_check.tick() => {
+                if let Some(track) = &track {
+                    update_current_line(track, &mut shown, playback_position(&playback));
+                }
+            }
+            _ = interrupt.recv() => break,
+            _ = terminate.recv() => break,
+        }
+    }
+
+    shutdown.trigger();
+    println!();
+    Ok(())
+}
+
+/// Fetch the track's lyrics in the background and send back what to show
+fn spawn_fetch(
+    fetcher: Arc<LyricsFetcher>,
+    track: TrackInfo,
+    no_lyrics_text: String,
+    lyrics_tx: mpsc::Sender<(String, Shown)>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let artist = track.artists.first().cloned().unwrap_or_default();
+        let shown = match fetcher
+            .fetch_lyrics(&track.id, &artist, &track.name, Some(track.duration_ms))
+            .await
+        {
+            Ok(LyricsResult::Synced(synced)) => Shown::Synced(synced, None),
+            Ok(LyricsResult::Plain(lyrics)) => Shown::Text(lyrics),
+            Ok(LyricsResult::None) => Shown::Text(no_lyrics_text),
+            Err(e) => {
+                log::warn!("Failed to fetch lyrics: {}", e);
+                Shown::Text("Failed to fetch lyrics".to_string())
+            }
+        };
+        let _ = lyrics_tx.send((track.id, shown)).await;
+    })
+}
+
+/// Forget the playing track, cancelling its lyrics fetch
+fn stop_track(
+    track: &mut Option<TrackInfo>,
+    fetch: &mut Option<JoinHandle<()>>,
+    playback: &mut Option<(PlaybackProgress, Instant)>,
+) {
+    if let Some(fetch) = fetch.take() {
+        fetch.abort();
+    }
+    *track = None;
+    *playback = None;
+}
+
+/// Where playback is now, advanced locally since the last report while playing
+fn playback_position(playback: &Option<(PlaybackProgress, Instant)>) -> Duration {
+    match playback {
+        Some((progress, received)) => {
+            let position = Duration::from_millis(progress.progress_ms);
+            if progress.is_playing {
+                position + received.elapsed()
+            } else {
+                position
+            }
+        }
+        None => Duration::ZERO,
+    }
+}
+
+/// Move the highlight of synced lyrics to the line at `position`, redrawing
+/// only if it changed
+fn update_current_line(track: &TrackInfo, shown: &mut Shown, position: Duration) {
+    if let Shown::Synced(synced, current) = shown {
+        let line = synced.line_at(position);
+        if line != *current {
+            *current = line;
+            draw_lyrics(track, shown);
+        }
+    }
+}
+
+/// Replace the screen with the track and its lyrics as currently shown
+fn draw_lyrics(track: &TrackInfo, shown: &Shown) {
+    match shown {
+        Shown::Text(text) => draw_track(track, text),
+        Shown::Synced(synced, current) => draw_track(track, &synced_text(synced, *current)),
+    }
+}
+
+/// Synced lyrics as text, with the current line in bold
+fn synced_text(synced: &SyncedLyrics, current: Option<usize>) -> String {
+    synced
+        .lines
+        .iter()
+        .enumerate()
+        .map(|(index, (_, line))| {
+            if Some(index) == current {
+                format!("{}{}{}", BOLD, line, RESET)
+            } else {
+                line.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Replace the screen with the track's name, artists and lyrics
+///
+/// The second line is left for the playback position, drawn separately on
+/// each progress report.
+fn draw_track(track: &TrackInfo, lyrics: &str) {
+    println!(
+        "{}{}{}{} — {}\n\n\n{}",
+        CLEAR,
+        BOLD,
+        track.name,
+        RESET,
+        track.artists.join(", "),
+        lyrics
+    );
+    let _ = io::stdout().flush();
+}
+
+/// Redraw the position line under the track name
+fn draw_position(progress: &PlaybackProgress) {
+    print!("{}{}{}{}", POSITION_LINE, DIM, position_text(progress), RESET);
+    let _ = io::stdout().flush();
+}
+
+/// Replace the screen with a status message
+fn draw_message(message: &str) {
+    println!("{}{}{}{}", CLEAR, DIM, message, RESET);
+    let _ = io::stdout().flush();
+}
+
+/// "1:23 / 4:56", with "(paused)" while paused
+fn position_text(progress: &PlaybackProgress) -> String {
+    let minutes_seconds = |ms: u64| format!("{}:{:02}", ms / 60_000, ms / 1000 % 60);
+    format!(
+        "{} / {}{}",
+        minutes_seconds(progress.progress_ms),
+        minutes_seconds(progress.duration_ms),
+        if progress.is_playing { "" } else { " (paused)" }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_text() {
+        let progress = PlaybackProgress {
+            progress_ms: 83_900,
+            duration_ms: 296_000,
+            is_playing: true,
+        };
+        assert_eq!(position_text(&progress), "1:23 / 4:56");
+
+        let paused = PlaybackProgress {
+            is_playing: false,
+            ..progress
+        };
+        assert_eq!(position_text(&paused), "1:23 / 4:56 (paused)");
+    }
+
+    #[test]
+    fn test_current_synced_line_is_bold() {
+        let synced = SyncedLyrics {
+            lines: vec![
+                (Duration::from_secs(5), "la la".to_string()),
+                (Duration::from_secs(9), "la".to_string()),
+            ],
+        };
+        assert_eq!(synced.line_at(Duration::from_secs(2)), None);
+        assert_eq!(synced.line_at(Duration::from_secs(9)), Some(1));
+
+        let current = synced.line_at(Duration::from_secs(6));
+        assert_eq!(current, Some(0));
+        assert_eq!(synced_text(&synced, current), format!("{}la la{}\nla", BOLD, RESET));
+        assert_eq!(synced_text(&synced, None), "la la\nla");
+    }
+}
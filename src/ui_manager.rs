@@ -1,54 +1,183 @@
 use crate::app_core::AppEvent;
-use crate::config::AppConfig;
+use crate::compat;
+use crate::config::{AppConfig, DebouncedSave, EntryAnimation, MenuStyle};
 use crate::error::{LyricsifyError, Result};
+use crate::log_privacy::logged;
+use crate::spotify_client::DeviceInfo;
+use crate::theme::Theme;
 use objc2::rc::Retained;
+use objc2::runtime::{AnyObject, ProtocolObject};
 use objc2::{declare_class, msg_send_id, mutability, ClassType, DeclaredClass};
 use objc2_app_kit::{
-    NSBackingStoreType, NSColor, NSFont, NSMenu, NSMenuItem, NSScreen, NSStatusBar, NSStatusItem,
-    NSTextView, NSVisualEffectView, NSVisualEffectBlendingMode, NSVisualEffectMaterial,
-    NSVisualEffectState, NSWindow, NSWindowCollectionBehavior, NSWindowStyleMask,
-    NSWindowTitleVisibility,
+    NSAccessibility, NSAlert, NSAlertFirstButtonReturn, NSAnimatablePropertyContainer,
+    NSAnimationContext, NSApplication, NSApplicationActivationPolicy, NSAutoresizingMaskOptions,
+    NSBackingStoreType, NSBorderType, NSColor, NSControlSize, NSControlStateValueOff,
+    NSControlStateValueOn, NSEvent, NSEventMask, NSEventModifierFlags, NSEventType, NSFont, NSImage,
+    NSMenu, NSMenuItem, NSModalResponseOK, NSMutableParagraphStyle, NSOpenPanel, NSPasteboard,
+    NSPasteboardTypeString, NSProgressIndicator, NSProgressIndicatorStyle, NSRunningApplication,
+    NSSavePanel, NSScreen, NSScrollView, NSStatusBar, NSStatusItem, NSTextAlignment, NSTextField,
+    NSTextView, NSVisualEffectBlendingMode, NSVisualEffectState, NSVisualEffectView, NSWindow,
+    NSWindowCollectionBehavior, NSWindowDelegate, NSWindowSharingType, NSWindowStyleMask,
+    NSWindowTitleVisibility, NSWorkspace, NSWorkspaceDidActivateApplicationNotification,
+    NSWorkspaceDidWakeNotification,
 };
-use objc2_foundation::{ns_string, CGPoint, CGRect, CGSize, MainThreadMarker, NSObject, NSString};
+use objc2_foundation::{
+    ns_string, CGPoint, CGRect, CGSize, MainThreadMarker, NSDefaultRunLoopMode, NSNotification,
+    NSObject, NSObjectProtocol, NSRange, NSString, NSUserNotification, NSUserNotificationCenter,
+};
+use std::cell::Cell;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
+/// Virtual key code of the Escape key
+const ESCAPE_KEY_CODE: u16 = 53;
+
+/// Virtual key codes of the up and down arrow keys
+const UP_ARROW_KEY_CODE: u16 = 126;
+const DOWN_ARROW_KEY_CODE: u16 = 125;
+
+/// Opacity change per ⌘⌥↑/↓ press
+const OPACITY_STEP: f64 = 0.1;
+
+/// Overlay opacity bounds; below the minimum the overlay is hard to find again
+const MIN_OPACITY: f64 = 0.2;
+const MAX_OPACITY: f64 = 1.0;
+
+/// How long a badge message stays on the overlay
+const BADGE_DURATION: Duration = Duration::from_millis(1500);
+
+/// Height of the overlay when collapsed to a single line while idle
+const COLLAPSED_HEIGHT: f64 = 36.0;
+
+/// Length of the overlay entry animation
+const ENTRY_ANIMATION_SECS: f64 = 0.25;
+
+/// How far (in points) from its target a slide may end and still count as arrived
+const SLIDE_TARGET_TOLERANCE: f64 = 1.0;
+
+/// Moves after this long are handled normally even if a slide never arrived
+/// (interrupted by a hide, a drag, or a screen change)
+const SLIDE_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// Length of the fade when the overlay dims or brightens with focus
+const DIM_ANIMATION_SECS: f64 = 0.4;
+
+/// Bundle identifier of the Spotify desktop app
+const SPOTIFY_BUNDLE_ID: &str = "com.spotify.client";
+
+/// Space between the menu bar and an overlay anchored below its icon
+const ANCHOR_GAP: f64 = 6.0;
+
+/// Screen size assumed for layout while no display is attached
+const FALLBACK_SCREEN_SIZE: CGSize = CGSize::new(1440.0, 900.0);
+
+/// Display a macOS notification
+///
+/// This function uses the native NSUserNotificationCenter API to display
+/// a notification to the user.
+#[allow(deprecated)] // UNUserNotificationCenter requires a signed app bundle
+pub fn show_notification(title: &str, message: &str) {
+    // Now playing notifications name the track
+    log::info!("Notification: {} - {}", title, logged(message));
+
+    let notification = NSUserNotification::new();
+    unsafe {
+        notification.setTitle(Some(&NSString::from_str(title)));
+        notification.setInformativeText(Some(&NSString::from_str(message)));
+        NSUserNotificationCenter::defaultUserNotificationCenter()
+            .deliverNotification(&notification);
+    }
+}
+
+/// Replace the clipboard contents with `text`, returning whether it succeeded
+pub fn copy_to_clipboard(text: &str) -> bool {
+    unsafe {
+        let pasteboard = NSPasteboard::generalPasteboard();
+        pasteboard.clearContents();
+        pasteboard.setString_forType(&NSString::from_str(text), NSPasteboardTypeString)
+    }
+}
+
+/// Whether Lyricsify itself or the Spotify app is the frontmost app
+pub fn app_or_player_frontmost() -> bool {
+    unsafe {
+        let own_pid = NSRunningApplication::currentApplication().processIdentifier();
+        NSWorkspace::sharedWorkspace()
+            .frontmostApplication()
+            .is_some_and(|app| {
+                app.processIdentifier() == own_pid
+                    || app
+                        .bundleIdentifier()
+                        .is_some_and(|id| id.to_string() == SPOTIFY_BUNDLE_ID)
+            })
+    }
+}
+
 /// Manages the overlay window for displaying lyrics
 pub struct OverlayWindow {
     window: Retained<NSWindow>,
     text_view: Retained<NSTextView>,
+    /// Scrolls the text view when the lyrics are longer than the window
+    scroll_view: Retained<NSScrollView>,
+    progress_bar: Option<Retained<NSProgressIndicator>>,
+    /// Brief status messages such as the opacity after a change
+    badge: Retained<NSTextField>,
+    /// When the badge message should disappear
+    badge_until: Cell<Option<Instant>>,
+    /// Retained here since the window only holds a weak reference to it
+    delegate: Retained<OverlayWindowDelegate>,
     current_position: Arc<Mutex<CGPoint>>,
     config: Arc<Mutex<AppConfig>>,
+    /// Pending write of `config`, shared with the delegate
+    config_save: Arc<DebouncedSave>,
+    /// Set when created without a screen, until the window is placed on one
+    awaiting_screen: Cell<bool>,
+    /// Window and scroll view frames to restore while collapsed
+    expanded_frames: Cell<Option<(CGRect, CGRect)>>,
+    /// Opacity while not dimmed, from the config or theme
+    full_opacity: Cell<f64>,
+    /// Whether the overlay is dimmed because another app is frontmost
+    dimmed: Cell<bool>,
+    mtm: MainThreadMarker,
 }
 
 impl OverlayWindow {
     /// Create a new overlay window with the given configuration
-    pub fn new(config: AppConfig) -> Result<Self> {
-        let mtm = unsafe { MainThreadMarker::new_unchecked() };
-
-        // Get screen dimensions for positioning
-        let screen = NSScreen::mainScreen(mtm)
-            .ok_or_else(|| LyricsifyError::UIError("Failed to get main screen".to_string()))?;
-        let screen_frame = screen.frame();
-
-        // Calculate default position (top-right corner)
-        let window_width = 400.0;
-        let window_height = 600.0;
-        let default_x = screen_frame.size.width - window_width - 20.0;
-        let default_y = screen_frame.size.height - window_height - 60.0;
+    ///
+    /// Requires a `MainThreadMarker` since AppKit windows may only be created
+    /// on the main thread.
+    pub fn new(config: AppConfig, mtm: MainThreadMarker) -> Result<Self> {
+        let theme = Theme::load(&config.theme);
+        let full_opacity = config.overlay_opacity.unwrap_or(theme.window_alpha);
+
+        // Get screen dimensions for positioning. With no display attached
+        // (clamshell mode, locked session) there is no main screen; lay out
+        // against a typical screen instead and move once one appears.
+        let main_screen = NSScreen::mainScreen(mtm);
+        let awaiting_screen = main_screen.is_none();
+        let screen_frame = match main_screen {
+            Some(screen) => screen.frame(),
+            None => {
+                log::warn!(
+                    "No main screen available, overlay will be placed once a display appears"
+                );
+                CGRect::new(CGPoint::new(0.0, 0.0), FALLBACK_SCREEN_SIZE)
+            }
+        };
 
-        // Use saved position or default
+        // Use saved position or default (top-right corner)
+        let window_size = CGSize::new(400.0, 600.0);
         let (x, y) = if config.window_position == (100.0, 100.0) {
-            (default_x, default_y)
+            let origin = default_origin(screen_frame, window_size);
+            (origin.x, origin.y)
         } else {
             config.window_position
         };
 
         // Create window frame
-        let window_rect = CGRect::new(
-            CGPoint::new(x, y),
-            CGSize::new(window_width, window_height),
-        );
+        let window_rect = CGRect::new(CGPoint::new(x, y), window_size);
 
         // Create window with appropriate style mask
         let style_mask = NSWindowStyleMask::Titled
@@ -80,7 +209,8 @@ impl OverlayWindow {
 
             // Set window opacity
             window.setOpaque(false);
-            window.setAlphaValue(0.8);
+            window.setAlphaValue(full_opacity);
+            window.setHasShadow(theme.shadow);
 
             // Set background color to clear
             window.setBackgroundColor(Some(&NSColor::clearColor()));
@@ -91,6 +221,15 @@ impl OverlayWindow {
 
             // Make window movable by background
             window.setMovableByWindowBackground(true);
+
+            // Title is hidden visually but still announced by VoiceOver
+            window.setTitle(ns_string!("Lyricsify"));
+
+            // Excluded windows stay on the local display but are left out of
+            // anything captured, so sharing never shows what's playing
+            if config.hide_from_screen_capture {
+                window.setSharingType(NSWindowSharingType::None);
+            }
         }
 
         // Create visual effect view for blur background
@@ -101,49 +240,135 @@ impl OverlayWindow {
 
         let effect_view = unsafe {
             let view = NSVisualEffectView::initWithFrame(mtm.alloc(), content_frame);
-            view.setMaterial(NSVisualEffectMaterial::HUDWindow);
+            view.setMaterial(compat::visual_effect_material(theme.material));
             view.setBlendingMode(NSVisualEffectBlendingMode::BehindWindow);
             view.setState(NSVisualEffectState::Active);
             view
         };
 
-        // Create text view for lyrics display
-        let text_frame = CGRect::new(
-            CGPoint::new(20.0, 20.0),
-            CGSize::new(content_frame.size.width - 40.0, content_frame.size.height - 40.0),
+        // Create a transparent scroll view so long lyrics can be scrolled
+        // while the blur shows through
+        let padding = theme.padding;
+        let scroll_frame = CGRect::new(
+            CGPoint::new(padding, padding),
+            CGSize::new(
+                content_frame.size.width - 2.0 * padding,
+                content_frame.size.height - 2.0 * padding,
+            ),
         );
 
+        let scroll_view = unsafe {
+            let sv = NSScrollView::initWithFrame(mtm.alloc(), scroll_frame);
+            sv.setDrawsBackground(false);
+            sv.setBorderType(NSBorderType::NSNoBorder);
+            sv.setHasVerticalScroller(true);
+            sv.setHasHorizontalScroller(false);
+            sv.setAutohidesScrollers(true);
+            sv
+        };
+
+        // Create text view for lyrics display
         let text_view = unsafe {
-            let tv = NSTextView::initWithFrame(mtm.alloc(), text_frame);
+            let text_size = scroll_view.contentSize();
+            let tv = NSTextView::initWithFrame(
+                mtm.alloc(),
+                CGRect::new(CGPoint::new(0.0, 0.0), text_size),
+            );
+
+            // Grow downwards with the text and follow the scroll view's width
+            tv.setMinSize(CGSize::new(0.0, text_size.height));
+            tv.setMaxSize(CGSize::new(f64::MAX, f64::MAX));
+            tv.setVerticallyResizable(true);
+            tv.setHorizontallyResizable(false);
+            tv.setAutoresizingMask(NSAutoresizingMaskOptions::NSViewWidthSizable);
 
             // Configure text view properties
             tv.setEditable(false);
             tv.setSelectable(true);
             tv.setBackgroundColor(&NSColor::clearColor());
 
-            // Set text color to white
-            tv.setTextColor(Some(&NSColor::whiteColor()));
-
-            // Set font to SF Pro Text, 14pt
-            let font = NSFont::systemFontOfSize(14.0);
+            // Apply the theme's text color, font and line spacing
+            let (red, green, blue, alpha) = theme.text_rgba();
+            tv.setTextColor(Some(&NSColor::colorWithSRGBRed_green_blue_alpha(
+                red, green, blue, alpha,
+            )));
+
+            let font = theme
+                .font_name
+                .as_deref()
+                .and_then(|name| {
+                    NSFont::fontWithName_size(&NSString::from_str(name), theme.font_size)
+                })
+                .unwrap_or_else(|| NSFont::systemFontOfSize(theme.font_size));
             tv.setFont(Some(&font));
 
-            // Configure text container for padding and line spacing
+            if theme.line_spacing > 0.0 {
+                let paragraph_style = NSMutableParagraphStyle::new();
+                paragraph_style.setLineSpacing(theme.line_spacing);
+                tv.setDefaultParagraphStyle(Some(&paragraph_style));
+            }
+
+            // Configure text container for padding and line spacing, wrapping
+            // at the view's width
             if let Some(text_container) = tv.textContainer() {
                 text_container.setLineFragmentPadding(0.0);
+                text_container.setContainerSize(CGSize::new(text_size.width, f64::MAX));
+                text_container.setWidthTracksTextView(true);
             }
 
             // Set initial text
             tv.setString(ns_string!("Initializing..."));
 
+            // Describe the view for VoiceOver; the value is updated on track change
+            tv.setAccessibilityLabel(Some(ns_string!("Lyrics")));
+
             tv
         };
 
-        // Add text view to effect view
+        // Put the text view in the scroll view, and that in the effect view
         unsafe {
-            effect_view.addSubview(&text_view);
+            scroll_view.setDocumentView(Some(&text_view));
+            effect_view.addSubview(&scroll_view);
         }
 
+        // Small label in the top-right corner for brief status messages
+        let badge = unsafe {
+            let label = compat::label("", mtm);
+            label.setFont(Some(&NSFont::systemFontOfSize(11.0)));
+            label.setTextColor(Some(&NSColor::secondaryLabelColor()));
+            label.setAlignment(NSTextAlignment::Right);
+            label.setFrame(CGRect::new(
+                CGPoint::new(content_frame.size.width - 140.0, content_frame.size.height - 24.0),
+                CGSize::new(120.0, 16.0),
+            ));
+            label.setHidden(true);
+            effect_view.addSubview(&label);
+            label
+        };
+
+        // Create a thin progress bar along the bottom edge if enabled
+        let progress_bar = if config.show_progress_bar {
+            let bar_frame = CGRect::new(
+                CGPoint::new(padding, 4.0),
+                CGSize::new(content_frame.size.width - 2.0 * padding, 12.0),
+            );
+
+            let bar = unsafe {
+                let bar = NSProgressIndicator::initWithFrame(mtm.alloc(), bar_frame);
+                bar.setStyle(NSProgressIndicatorStyle::Bar);
+                bar.setControlSize(NSControlSize::Small);
+                bar.setIndeterminate(false);
+                bar.setMinValue(0.0);
+                bar.setMaxValue(1.0);
+                bar.setDoubleValue(0.0);
+                effect_view.addSubview(&bar);
+                bar
+            };
+            Some(bar)
+        } else {
+            None
+        };
+
         // Set effect view as content view
         window.setContentView(Some(&effect_view));
 
@@ -154,44 +379,208 @@ impl OverlayWindow {
 
         let current_position = Arc::new(Mutex::new(CGPoint::new(x, y)));
         let config_arc = Arc::new(Mutex::new(config));
+        let config_save = Arc::new(DebouncedSave::default());
+
+        // Snap to screen edges and persist the position when the window is moved
+        let delegate = OverlayWindowDelegate::new(
+            window.clone(),
+            Arc::clone(&config_arc),
+            Arc::clone(&config_save),
+            Arc::clone(&current_position),
+            mtm,
+        );
+        unsafe {
+            window.setDelegate(Some(ProtocolObject::from_ref(&*delegate)));
+        }
 
         Ok(Self {
             window,
             text_view,
+            scroll_view,
+            progress_bar,
+            badge,
+            badge_until: Cell::new(None),
+            delegate,
             current_position,
             config: config_arc,
+            config_save,
+            awaiting_screen: Cell::new(awaiting_screen),
+            expanded_frames: Cell::new(None),
+            full_opacity: Cell::new(full_opacity),
+            dimmed: Cell::new(false),
+            mtm,
         })
     }
 
-    /// Show the overlay window
-    pub fn show(&self) -> Result<()> {
-        self.window.makeKeyAndOrderFront(None);
+    /// Move the overlay to its default spot once a main screen exists
+    ///
+    /// Only does anything if there was no screen when the overlay was created;
+    /// a saved position is left alone since it was chosen on a real screen.
+    pub fn place_on_screen_if_pending(&self) {
+        if !self.awaiting_screen.get() {
+            return;
+        }
+        let Some(screen) = NSScreen::mainScreen(self.mtm) else {
+            return;
+        };
+        self.awaiting_screen.set(false);
+
+        let uses_default_position = self
+            .config
+            .lock()
+            .map(|config| config.window_position == (100.0, 100.0))
+            .unwrap_or(true);
+        if uses_default_position {
+            let origin = default_origin(screen.frame(), self.window.frame().size);
+            unsafe {
+                self.window.setFrameOrigin(origin);
+            }
+        }
+        log::info!("Main screen available, overlay placed on it");
+    }
+
+    /// Move the overlay just below the menu bar icon at `icon_frame`,
+    /// centered on it but kept within `bounds`
+    pub fn anchor_below(&self, icon_frame: CGRect, bounds: CGRect) {
+        let origin = anchored_origin(icon_frame, self.window.frame().size, bounds);
         unsafe {
-            self.window.orderFrontRegardless();
+            self.window.setFrameOrigin(origin);
         }
+    }
+
+    /// Show the overlay window
+    pub fn show(&self) -> Result<()> {
+        let animation = self
+            .config
+            .lock()
+            .map(|config| config.entry_animation)
+            .unwrap_or_default();
+        self.animate_entry(animation);
 
         // Update config
         if let Ok(mut config) = self.config.lock() {
             config.overlay_visible = true;
-            let _ = config.save();
+            self.config_save.request();
         }
 
         Ok(())
     }
 
+    /// Order the window front, animated as configured
+    ///
+    /// Slides become fades when Reduce Motion is on, as Apple recommends.
+    fn animate_entry(&self, animation: EntryAnimation) {
+        let animation = match animation {
+            EntryAnimation::Slide if compat::reduce_motion_enabled() => EntryAnimation::Fade,
+            animation => animation,
+        };
+
+        let final_frame = self.window.frame();
+        // Not the current alpha, which may be partway through a fade
+        let final_alpha = self.full_opacity.get();
+        let screen_bounds = unsafe { self.window.screen() }.map(|screen| screen.visibleFrame());
+
+        match (animation, screen_bounds) {
+            (EntryAnimation::Fade, _) => self.window.setAlphaValue(0.0),
+            (EntryAnimation::Slide, Some(bounds)) => unsafe {
+                self.delegate.begin_slide(final_frame.origin);
+                self.window.setFrameOrigin(slide_start_origin(final_frame, bounds));
+            },
+            _ => {}
+        }
+
+        self.window.makeKeyAndOrderFront(None);
+        unsafe {
+            self.window.orderFrontRegardless();
+        }
+
+        if animation == EntryAnimation::None {
+            return;
+        }
+        unsafe {
+            NSAnimationContext::beginGrouping();
+            NSAnimationContext::currentContext(self.mtm).setDuration(ENTRY_ANIMATION_SECS);
+            let animator = self.window.animator();
+            match animation {
+                EntryAnimation::Fade => animator.setAlphaValue(final_alpha),
+                _ => animator.setFrame_display(final_frame, true),
+            }
+            NSAnimationContext::endGrouping();
+        }
+    }
+
     /// Hide the overlay window
     pub fn hide(&self) -> Result<()> {
         self.window.orderOut(None);
+        self.delegate.end_slide();
 
         // Update config
         if let Ok(mut config) = self.config.lock() {
             config.overlay_visible = false;
-            let _ = config.save();
+            self.config_save.request();
         }
 
         Ok(())
     }
 
+    /// Shrink the overlay to a single-line bar showing `message`
+    ///
+    /// The bottom-left origin is kept so the saved window position isn't
+    /// changed; `expand` restores the previous size.
+    pub fn collapse(&self, message: &str) -> Result<()> {
+        if self.expanded_frames.get().is_none() {
+            let frame = self.window.frame();
+            let scroll_frame = self.scroll_view.frame();
+            self.expanded_frames.set(Some((frame, scroll_frame)));
+
+            let collapsed_frame = CGRect::new(
+                frame.origin,
+                CGSize::new(frame.size.width, COLLAPSED_HEIGHT),
+            );
+            let collapsed_scroll_frame = CGRect::new(
+                CGPoint::new(scroll_frame.origin.x, 8.0),
+                CGSize::new(scroll_frame.size.width, COLLAPSED_HEIGHT - 16.0),
+            );
+            unsafe {
+                if let Some(bar) = &self.progress_bar {
+                    bar.setHidden(true);
+                }
+                self.scroll_view.setFrame(collapsed_scroll_frame);
+            }
+            self.window.setFrame_display(collapsed_frame, true);
+        }
+
+        self.update_lyrics(message)
+    }
+
+    /// Restore the size the overlay had before `collapse` (no-op if not collapsed)
+    pub fn expand(&self) -> Result<()> {
+        if let Some((frame, scroll_frame)) = self.expanded_frames.take() {
+            self.window.setFrame_display(frame, true);
+            unsafe {
+                self.scroll_view.setFrame(scroll_frame);
+                if let Some(bar) = &self.progress_bar {
+                    bar.setHidden(false);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Take the overlay off screen without changing the saved visibility
+    pub fn conceal(&self) -> Result<()> {
+        self.window.orderOut(None);
+        Ok(())
+    }
+
+    /// Bring back an overlay hidden with `conceal`
+    pub fn reveal(&self) -> Result<()> {
+        unsafe {
+            self.window.orderFrontRegardless();
+        }
+        Ok(())
+    }
+
     /// Update the lyrics displayed in the overlay
     pub fn update_lyrics(&self, lyrics: &str) -> Result<()> {
         let text = NSString::from_str(lyrics);
@@ -201,6 +590,55 @@ impl OverlayWindow {
         Ok(())
     }
 
+    /// Draw pending changes right away
+    ///
+    /// Drawing normally happens on the next event pump, which doesn't run
+    /// while the app awaits a fetch, so text set just before one needs this.
+    pub fn display_now(&self) {
+        self.window.displayIfNeeded();
+    }
+
+    /// Scroll the lyrics back to the first line
+    pub fn scroll_to_top(&self) -> Result<()> {
+        unsafe {
+            self.text_view.scrollRangeToVisible(NSRange::new(0, 0));
+        }
+        Ok(())
+    }
+
+    /// Update the track progress bar (no-op when the bar is disabled)
+    pub fn update_progress(&self, progress_ms: u64, duration_ms: u64) -> Result<()> {
+        if let Some(bar) = &self.progress_bar {
+            let fraction = if duration_ms > 0 {
+                (progress_ms as f64 / duration_ms as f64).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            unsafe {
+                bar.setDoubleValue(fraction);
+            }
+        }
+        Ok(())
+    }
+
+    /// Expose the now-playing description to VoiceOver
+    ///
+    /// The text view's contents are already readable, so the track is
+    /// announced through the accessibility value rather than the lyrics text.
+    pub fn set_now_playing(&self, now_playing: Option<&str>) -> Result<()> {
+        let description = NSString::from_str(now_playing.unwrap_or("Nothing playing"));
+        let value: &AnyObject = &description;
+        unsafe {
+            self.text_view.setAccessibilityValue(Some(value));
+        }
+        Ok(())
+    }
+
+    /// The window position as stored in the overlay's copy of the config
+    pub fn saved_position(&self) -> Option<(f64, f64)> {
+        self.config.lock().ok().map(|config| config.window_position)
+    }
+
     /// Get the current window position
     pub fn get_position(&self) -> CGPoint {
         let frame = self.window.frame();
@@ -221,26 +659,244 @@ impl OverlayWindow {
         // Save to config
         if let Ok(mut config) = self.config.lock() {
             config.window_position = (point.x, point.y);
-            let _ = config.save();
+            self.config_save.request();
         }
 
         Ok(())
     }
 
+    /// Replace the overlay's copy of the config, e.g. after importing settings
+    ///
+    /// The overlay saves its copy when moved or shown, which would otherwise
+    /// write the old settings back.
+    pub fn replace_config(&self, config: AppConfig) {
+        if let Ok(mut current) = self.config.lock() {
+            *current = config;
+        }
+    }
+
+    /// Write the overlay's config if a save requested by a live setter is due
+    pub fn save_config_if_due(&self) {
+        if self.config_save.take_due(Instant::now()) {
+            if let Ok(config) = self.config.lock() {
+                let _ = config.save();
+            }
+        }
+    }
+
+    /// Write the overlay's config now if any save is pending, e.g. on quit
+    pub fn flush_config(&self) -> Result<()> {
+        if !self.config_save.take_pending() {
+            return Ok(());
+        }
+        match self.config.lock() {
+            Ok(config) => config.save(),
+            Err(_) => Err(LyricsifyError::UIError("Overlay config lock poisoned".to_string())),
+        }
+    }
+
     /// Check if the overlay is currently visible
     pub fn is_visible(&self) -> bool {
         self.window.isVisible()
     }
+
+    /// Change the overlay opacity by `delta` and save it, returning the new value
+    pub fn adjust_opacity(&self, delta: f64) -> f64 {
+        let opacity = (self.full_opacity.get() + delta).clamp(MIN_OPACITY, MAX_OPACITY);
+        // Keep steps on round values despite float drift
+        let opacity = (opacity * 100.0).round() / 100.0;
+        self.full_opacity.set(opacity);
+        self.dimmed.set(false);
+        self.window.setAlphaValue(opacity);
+
+        if let Ok(mut config) = self.config.lock() {
+            config.overlay_opacity = Some(opacity);
+            self.config_save.request();
+        }
+        opacity
+    }
+
+    /// Fade the overlay down to `dimmed_opacity`, or back up to its full opacity
+    ///
+    /// The change is immediate when Reduce Motion is on.
+    pub fn set_dimmed(&self, dimmed: bool, dimmed_opacity: f64) {
+        if self.dimmed.replace(dimmed) == dimmed {
+            return;
+        }
+
+        let full_opacity = self.full_opacity.get();
+        let target = if dimmed {
+            dimmed_opacity.clamp(MIN_OPACITY.min(full_opacity), full_opacity)
+        } else {
+            full_opacity
+        };
+
+        if compat::reduce_motion_enabled() {
+            self.window.setAlphaValue(target);
+            return;
+        }
+        unsafe {
+            NSAnimationContext::beginGrouping();
+            NSAnimationContext::currentContext(self.mtm).setDuration(DIM_ANIMATION_SECS);
+            self.window.animator().setAlphaValue(target);
+            NSAnimationContext::endGrouping();
+        }
+    }
+
+    /// Show `message` in the corner of the overlay for a moment
+    pub fn flash_badge(&self, message: &str) {
+        unsafe {
+            self.badge.setStringValue(&NSString::from_str(message));
+            self.badge.setHidden(false);
+        }
+        self.badge_until.set(Some(Instant::now() + BADGE_DURATION));
+    }
+
+    /// Hide the badge once its time is up
+    fn expire_badge(&self) {
+        if self.badge_until.get().is_some_and(|until| Instant::now() >= until) {
+            self.badge_until.set(None);
+            unsafe { self.badge.setHidden(true) };
+        }
+    }
 }
 
 pub struct UIManager {
+    application: Retained<NSApplication>,
     overlay_window: Option<OverlayWindow>,
+    event_tx: mpsc::UnboundedSender<AppEvent>,
+    /// Whether Escape pressed on the focused overlay hides it
+    escape_hides_overlay: bool,
+    mtm: MainThreadMarker,
 }
 
 impl UIManager {
-    pub fn new(config: AppConfig) -> Result<Self> {
-        let overlay_window = Some(OverlayWindow::new(config)?);
-        Ok(Self { overlay_window })
+    pub fn new(
+        config: AppConfig,
+        event_tx: mpsc::UnboundedSender<AppEvent>,
+        mtm: MainThreadMarker,
+    ) -> Result<Self> {
+        // Set up the shared application before creating any windows. As an
+        // accessory app we get a status item but no Dock icon or main menu.
+        let application = NSApplication::sharedApplication(mtm);
+        application.setActivationPolicy(NSApplicationActivationPolicy::Accessory);
+        unsafe {
+            application.finishLaunching();
+        }
+
+        let escape_hides_overlay = config.escape_hides_overlay;
+        let overlay_window = Some(OverlayWindow::new(config, mtm)?);
+        Ok(Self {
+            application,
+            overlay_window,
+            event_tx,
+            escape_hides_overlay,
+            mtm,
+        })
+    }
+
+    /// Dispatch all pending AppKit events without blocking
+    ///
+    /// We never call `NSApplication::run` since the main thread is driven by
+    /// the Tokio event loop, so this must be called regularly from there for
+    /// menu clicks, window dragging, and redraws to be processed.
+    pub fn pump_events(&self) {
+        loop {
+            // A nil expiration date returns immediately when no event is queued
+            let event = unsafe {
+                self.application.nextEventMatchingMask_untilDate_inMode_dequeue(
+                    NSEventMask::Any,
+                    None,
+                    NSDefaultRunLoopMode,
+                    true,
+                )
+            };
+
+            let Some(event) = event else {
+                break;
+            };
+
+            // Escape is swallowed here rather than dispatched, so nothing in
+            // the text view (e.g. completion) reacts to it
+            if self.is_overlay_escape(&event) {
+                let _ = self.event_tx.send(AppEvent::HideOverlay);
+                continue;
+            }
+
+            if let Some(delta) = self.opacity_shortcut(&event) {
+                let _ = self.event_tx.send(AppEvent::AdjustOpacity(delta));
+                continue;
+            }
+
+            unsafe { self.application.sendEvent(&event) };
+        }
+
+        // Cheap no-ops unless the overlay was created without a screen or
+        // a badge is showing
+        if let Some(overlay) = &self.overlay_window {
+            overlay.place_on_screen_if_pending();
+            overlay.expire_badge();
+        }
+
+        unsafe {
+            self.application.updateWindows();
+        }
+    }
+
+    /// Opacity change for ⌘⌥↑ or ⌘⌥↓ pressed on the overlay, if that's what `event` is
+    fn opacity_shortcut(&self, event: &NSEvent) -> Option<f64> {
+        let overlay = self.overlay_window.as_ref()?;
+
+        unsafe {
+            if event.r#type() != NSEventType::KeyDown
+                || !event
+                    .window(self.mtm)
+                    .is_some_and(|window| std::ptr::eq(&*window, &*overlay.window))
+            {
+                return None;
+            }
+
+            let modifiers = event.modifierFlags()
+                & NSEventModifierFlags::NSEventModifierFlagDeviceIndependentFlagsMask;
+            let command_option = NSEventModifierFlags::NSEventModifierFlagCommand
+                | NSEventModifierFlags::NSEventModifierFlagOption;
+            // Arrow keys also carry the function and numeric pad flags
+            if modifiers
+                & !(NSEventModifierFlags::NSEventModifierFlagFunction
+                    | NSEventModifierFlags::NSEventModifierFlagNumericPad)
+                != command_option
+            {
+                return None;
+            }
+
+            match event.keyCode() {
+                UP_ARROW_KEY_CODE => Some(OPACITY_STEP),
+                DOWN_ARROW_KEY_CODE => Some(-OPACITY_STEP),
+                _ => None,
+            }
+        }
+    }
+
+    /// Whether `event` is an Escape key press that should hide the overlay
+    ///
+    /// Only applies while the overlay is the key window, so Escape keeps its
+    /// usual meaning elsewhere. Other keys (and mouse-driven text selection)
+    /// are dispatched normally.
+    fn is_overlay_escape(&self, event: &NSEvent) -> bool {
+        if !self.escape_hides_overlay {
+            return false;
+        }
+        let Some(overlay) = &self.overlay_window else {
+            return false;
+        };
+
+        unsafe {
+            event.r#type() == NSEventType::KeyDown
+                && event.keyCode() == ESCAPE_KEY_CODE
+                && event
+                    .window(self.mtm)
+                    .is_some_and(|window| std::ptr::eq(&*window, &*overlay.window))
+        }
     }
 
     pub fn overlay_window(&self) -> Option<&OverlayWindow> {
@@ -252,6 +908,190 @@ impl UIManager {
     }
 }
 
+/// Default overlay origin: the top-right corner of the screen, below the menu bar
+fn default_origin(screen_frame: CGRect, window_size: CGSize) -> CGPoint {
+    CGPoint::new(
+        screen_frame.size.width - window_size.width - 20.0,
+        screen_frame.size.height - window_size.height - 60.0,
+    )
+}
+
+/// Overlay origin placing a window of `size` centered just below `icon`,
+/// moved sideways and up as needed to stay within `bounds`
+fn anchored_origin(icon: CGRect, size: CGSize, bounds: CGRect) -> CGPoint {
+    let centered_x = icon.origin.x + (icon.size.width - size.width) / 2.0;
+    let max_x = bounds.origin.x + bounds.size.width - size.width;
+    CGPoint::new(
+        centered_x.min(max_x).max(bounds.origin.x),
+        (icon.origin.y - size.height - ANCHOR_GAP).max(bounds.origin.y),
+    )
+}
+
+/// Origin just outside the edge of `bounds` nearest to `frame`, for sliding
+/// the window in from there to where it is
+fn slide_start_origin(frame: CGRect, bounds: CGRect) -> CGPoint {
+    let left_gap = frame.origin.x - bounds.origin.x;
+    let right_gap = bounds.origin.x + bounds.size.width - (frame.origin.x + frame.size.width);
+    let bottom_gap = frame.origin.y - bounds.origin.y;
+    let top_gap = bounds.origin.y + bounds.size.height - (frame.origin.y + frame.size.height);
+
+    let mut origin = frame.origin;
+    let nearest = left_gap.min(right_gap).min(bottom_gap).min(top_gap);
+    if nearest == left_gap {
+        origin.x = bounds.origin.x - frame.size.width;
+    } else if nearest == right_gap {
+        origin.x = bounds.origin.x + bounds.size.width;
+    } else if nearest == bottom_gap {
+        origin.y = bounds.origin.y - frame.size.height;
+    } else {
+        origin.y = bounds.origin.y + bounds.size.height;
+    }
+    origin
+}
+
+/// Compute the origin of `frame` after snapping it flush to any edge of
+/// `bounds` that it is within `threshold` points of
+fn snap_to_edges(frame: CGRect, bounds: CGRect, threshold: f64) -> CGPoint {
+    let mut origin = frame.origin;
+
+    let left_gap = frame.origin.x - bounds.origin.x;
+    let right_gap =
+        (bounds.origin.x + bounds.size.width) - (frame.origin.x + frame.size.width);
+    if left_gap.abs() <= threshold {
+        origin.x = bounds.origin.x;
+    } else if right_gap.abs() <= threshold {
+        origin.x = bounds.origin.x + bounds.size.width - frame.size.width;
+    }
+
+    let bottom_gap = frame.origin.y - bounds.origin.y;
+    let top_gap =
+        (bounds.origin.y + bounds.size.height) - (frame.origin.y + frame.size.height);
+    if bottom_gap.abs() <= threshold {
+        origin.y = bounds.origin.y;
+    } else if top_gap.abs() <= threshold {
+        origin.y = bounds.origin.y + bounds.size.height - frame.size.height;
+    }
+
+    origin
+}
+
+// Declare a window delegate that snaps the overlay to screen edges after a move
+struct OverlayWindowDelegateIvars {
+    window: Retained<NSWindow>,
+    config: Arc<Mutex<AppConfig>>,
+    config_save: Arc<DebouncedSave>,
+    current_position: Arc<Mutex<CGPoint>>,
+    /// Where an entry slide ends and when it must have ended by; moves on
+    /// the way there aren't saved or snapped
+    slide_target: Cell<Option<(CGPoint, Instant)>>,
+}
+
+declare_class!(
+    struct OverlayWindowDelegate;
+
+    unsafe impl ClassType for OverlayWindowDelegate {
+        type Super = NSObject;
+        type Mutability = mutability::MainThreadOnly;
+        const NAME: &'static str = "OverlayWindowDelegate";
+    }
+
+    impl DeclaredClass for OverlayWindowDelegate {
+        type Ivars = OverlayWindowDelegateIvars;
+    }
+
+    unsafe impl NSObjectProtocol for OverlayWindowDelegate {}
+
+    unsafe impl NSWindowDelegate for OverlayWindowDelegate {
+        #[method(windowDidMove:)]
+        fn window_did_move(&self, _notification: &NSNotification) {
+            self.handle_move();
+        }
+    }
+);
+
+impl OverlayWindowDelegate {
+    fn new(
+        window: Retained<NSWindow>,
+        config: Arc<Mutex<AppConfig>>,
+        config_save: Arc<DebouncedSave>,
+        current_position: Arc<Mutex<CGPoint>>,
+        mtm: MainThreadMarker,
+    ) -> Retained<Self> {
+        let this = mtm.alloc::<Self>();
+        let this = this.set_ivars(OverlayWindowDelegateIvars {
+            window,
+            config,
+            config_save,
+            current_position,
+            slide_target: Cell::new(None),
+        });
+        unsafe { msg_send_id![super(this), init] }
+    }
+
+    /// Ignore moves until the window reaches `target` at the end of a slide,
+    /// or `SLIDE_TIMEOUT` passes
+    fn begin_slide(&self, target: CGPoint) {
+        self.ivars().slide_target.set(Some((target, Instant::now() + SLIDE_TIMEOUT)));
+    }
+
+    /// Stop ignoring moves, e.g. when a slide is cut short by hiding
+    fn end_slide(&self) {
+        self.ivars().slide_target.set(None);
+    }
+
+    /// Snap the window to nearby screen edges and save its new position
+    fn handle_move(&self) {
+        let ivars = self.ivars();
+        if let Some((target, deadline)) = ivars.slide_target.get() {
+            let origin = ivars.window.frame().origin;
+            let arrived = (origin.x - target.x).abs() <= SLIDE_TARGET_TOLERANCE
+                && (origin.y - target.y).abs() <= SLIDE_TARGET_TOLERANCE;
+            let timed_out = Instant::now() >= deadline;
+            if arrived || timed_out {
+                ivars.slide_target.set(None);
+            }
+            // After a stalled slide, this move is handled like any other
+            if !timed_out || arrived {
+                return;
+            }
+        }
+        let frame = ivars.window.frame();
+        let mut origin = frame.origin;
+
+        // The config lock is released before moving the window, since the
+        // move calls back into this method on the same thread
+        {
+            let Ok(mut config) = ivars.config.lock() else {
+                return;
+            };
+
+            if config.edge_snap_enabled {
+                if let Some(screen) = unsafe { ivars.window.screen() } {
+                    let bounds = unsafe { screen.visibleFrame() };
+                    origin = snap_to_edges(frame, bounds, config.edge_snap_threshold);
+                }
+            }
+
+            if config.window_position != (origin.x, origin.y) {
+                config.window_position = (origin.x, origin.y);
+                ivars.config_save.request();
+            }
+        }
+
+        if let Ok(mut pos) = ivars.current_position.lock() {
+            *pos = origin;
+        }
+
+        if origin.x != frame.origin.x || origin.y != frame.origin.y {
+            // Triggers another windowDidMove; the window is then already at
+            // the snapped origin, so that call changes nothing
+            unsafe {
+                ivars.window.setFrameOrigin(origin);
+            }
+        }
+    }
+}
+
 // Declare a custom delegate class for handling menu actions
 struct MenuBarDelegateIvars {
     event_tx: mpsc::UnboundedSender<AppEvent>,
@@ -276,6 +1116,83 @@ declare_class!(
             let _ = self.ivars().event_tx.send(AppEvent::ToggleOverlay);
         }
 
+        #[method(toggleNotifications:)]
+        fn toggle_notifications(&self, _sender: *const NSMenuItem) {
+            let _ = self.ivars().event_tx.send(AppEvent::ToggleNotifications);
+        }
+
+        #[method(skipTrack:)]
+        fn skip_track(&self, _sender: *const NSMenuItem) {
+            let _ = self.ivars().event_tx.send(AppEvent::SkipCurrentTrack);
+        }
+
+        #[method(clearSkippedTracks:)]
+        fn clear_skipped_tracks(&self, _sender: *const NSMenuItem) {
+            let _ = self.ivars().event_tx.send(AppEvent::ClearSkippedTracks);
+        }
+
+        #[method(runDiagnostics:)]
+        fn run_diagnostics(&self, _sender: *const NSMenuItem) {
+            let _ = self.ivars().event_tx.send(AppEvent::RunDiagnostics);
+        }
+
+        #[method(copyDiagnostics:)]
+        fn copy_diagnostics(&self, _sender: *const NSMenuItem) {
+            let _ = self.ivars().event_tx.send(AppEvent::CopyDiagnostics);
+        }
+
+        #[method(cycleLogLevel:)]
+        fn cycle_log_level(&self, _sender: *const NSMenuItem) {
+            let _ = self.ivars().event_tx.send(AppEvent::CycleLogLevel);
+        }
+
+        #[method(exportSettings:)]
+        fn export_settings(&self, _sender: *const NSMenuItem) {
+            let _ = self.ivars().event_tx.send(AppEvent::ExportSettings);
+        }
+
+        #[method(importSettings:)]
+        fn import_settings(&self, _sender: *const NSMenuItem) {
+            let _ = self.ivars().event_tx.send(AppEvent::ImportSettings);
+        }
+
+        #[method(refreshToken:)]
+        fn refresh_token(&self, _sender: *const NSMenuItem) {
+            let _ = self.ivars().event_tx.send(AppEvent::RefreshToken);
+        }
+
+        #[method(restartPolling:)]
+        fn restart_polling(&self, _sender: *const NSMenuItem) {
+            let _ = self.ivars().event_tx.send(AppEvent::RestartPolling);
+        }
+
+        #[method(fetchLyricsNow:)]
+        fn fetch_lyrics_now(&self, _sender: *const NSMenuItem) {
+            let _ = self.ivars().event_tx.send(AppEvent::FetchLyricsNow);
+        }
+
+        #[method(lookUpLyrics:)]
+        fn look_up_lyrics(&self, _sender: *const NSMenuItem) {
+            let _ = self.ivars().event_tx.send(AppEvent::ManualLookup);
+        }
+
+        #[method(endLookup:)]
+        fn end_lookup(&self, _sender: *const NSMenuItem) {
+            let _ = self.ivars().event_tx.send(AppEvent::EndManualLookup);
+        }
+
+        #[method(selectDevice:)]
+        fn select_device(&self, sender: &NSMenuItem) {
+            // The tag holds the device's index in the list the menu was built from
+            let index = unsafe { sender.tag() };
+            let _ = self.ivars().event_tx.send(AppEvent::SelectDevice(index as usize));
+        }
+
+        #[method(refreshDevices:)]
+        fn refresh_devices(&self, _sender: *const NSMenuItem) {
+            let _ = self.ivars().event_tx.send(AppEvent::RefreshDevices);
+        }
+
         #[method(authenticate:)]
         fn authenticate(&self, _sender: *const NSMenuItem) {
             let _ = self.ivars().event_tx.send(AppEvent::Authenticate);
@@ -285,6 +1202,17 @@ declare_class!(
         fn quit(&self, _sender: *const NSMenuItem) {
             let _ = self.ivars().event_tx.send(AppEvent::Quit);
         }
+
+        #[method(systemDidWake:)]
+        fn system_did_wake(&self, _notification: &NSNotification) {
+            let _ = self.ivars().event_tx.send(AppEvent::SystemWoke);
+        }
+
+        #[method(appDidActivate:)]
+        fn app_did_activate(&self, _notification: &NSNotification) {
+            let focused = app_or_player_frontmost();
+            let _ = self.ivars().event_tx.send(AppEvent::FocusChanged(focused));
+        }
     }
 );
 
@@ -296,22 +1224,131 @@ impl MenuBarDelegate {
     }
 }
 
+/// Ask for a line of text in a modal alert, `None` if cancelled or left empty
+///
+/// The app is activated first, since as an accessory app its alerts would
+/// otherwise open behind the frontmost window.
+pub fn prompt_for_text(
+    title: &str,
+    message: &str,
+    placeholder: &str,
+    confirm: &str,
+    mtm: MainThreadMarker,
+) -> Option<String> {
+    unsafe {
+        let alert = NSAlert::new(mtm);
+        alert.setMessageText(&NSString::from_str(title));
+        alert.setInformativeText(&NSString::from_str(message));
+        alert.addButtonWithTitle(&NSString::from_str(confirm));
+        alert.addButtonWithTitle(ns_string!("Cancel"));
+
+        let field = NSTextField::initWithFrame(
+            mtm.alloc(),
+            CGRect::new(CGPoint::new(0.0, 0.0), CGSize::new(280.0, 24.0)),
+        );
+        field.setPlaceholderString(Some(&NSString::from_str(placeholder)));
+        alert.setAccessoryView(Some(&field));
+        alert.window().setInitialFirstResponder(Some(&field));
+
+        NSApplication::sharedApplication(mtm).activateIgnoringOtherApps(true);
+        if alert.runModal() != NSAlertFirstButtonReturn {
+            return None;
+        }
+
+        let text = field.stringValue().to_string();
+        let text = text.trim();
+        (!text.is_empty()).then(|| text.to_string())
+    }
+}
+
+/// Ask where to save a file, `None` if cancelled
+pub fn choose_save_path(suggested_name: &str, mtm: MainThreadMarker) -> Option<PathBuf> {
+    unsafe {
+        let panel = NSSavePanel::savePanel(mtm);
+        panel.setNameFieldStringValue(&NSString::from_str(suggested_name));
+
+        NSApplication::sharedApplication(mtm).activateIgnoringOtherApps(true);
+        if panel.runModal() != NSModalResponseOK {
+            return None;
+        }
+        Some(PathBuf::from(panel.URL()?.path()?.to_string()))
+    }
+}
+
+/// Ask for a file to open, `None` if cancelled
+pub fn choose_open_path(mtm: MainThreadMarker) -> Option<PathBuf> {
+    unsafe {
+        let panel = NSOpenPanel::openPanel(mtm);
+        panel.setCanChooseDirectories(false);
+        panel.setAllowsMultipleSelection(false);
+
+        NSApplication::sharedApplication(mtm).activateIgnoringOtherApps(true);
+        if panel.runModal() != NSModalResponseOK {
+            return None;
+        }
+        Some(PathBuf::from(panel.URL()?.path()?.to_string()))
+    }
+}
+
+/// Load an SF Symbol as a template image for the status item
+///
+/// Returns `None` on macOS versions without SF Symbols (before 11) or if the
+/// symbol name is unknown, so the caller can fall back to a text glyph.
+fn status_symbol_image(symbol_name: &str) -> Option<Retained<NSImage>> {
+    if !compat::sf_symbols_available() {
+        log::info!("SF Symbols unavailable, using text status icon");
+        return None;
+    }
+
+    let image = unsafe {
+        NSImage::imageWithSystemSymbolName_accessibilityDescription(
+            &NSString::from_str(symbol_name),
+            Some(ns_string!("Lyricsify")),
+        )
+    };
+    match image {
+        Some(image) => {
+            // Template images follow the menu bar's light/dark appearance
+            unsafe { image.setTemplate(true) };
+            Some(image)
+        }
+        None => {
+            log::warn!("Unknown SF Symbol {:?}, using text status icon", symbol_name);
+            None
+        }
+    }
+}
+
 /// Manages the menu bar status item and dropdown menu
 pub struct MenuBar {
     status_item: Retained<NSStatusItem>,
     menu: Retained<NSMenu>,
     toggle_item: Retained<NSMenuItem>,
+    up_next_item: Retained<NSMenuItem>,
+    notifications_item: Retained<NSMenuItem>,
+    skip_item: Retained<NSMenuItem>,
+    clear_skipped_item: Retained<NSMenuItem>,
+    fetch_now_item: Retained<NSMenuItem>,
+    end_lookup_item: Retained<NSMenuItem>,
     auth_item: Retained<NSMenuItem>,
+    refresh_item: Retained<NSMenuItem>,
+    restart_polling_item: Retained<NSMenuItem>,
+    devices_item: Retained<NSMenuItem>,
+    devices_menu: Retained<NSMenu>,
     delegate: Retained<MenuBarDelegate>,
+    mtm: MainThreadMarker,
     overlay_visible: Arc<Mutex<bool>>,
     authenticated: Arc<Mutex<bool>>,
 }
 
 impl MenuBar {
     /// Create a new menu bar with status item
-    pub fn new(event_tx: mpsc::UnboundedSender<AppEvent>) -> Result<Self> {
-        let mtm = unsafe { MainThreadMarker::new_unchecked() };
-
+    pub fn new(
+        event_tx: mpsc::UnboundedSender<AppEvent>,
+        symbol_name: &str,
+        menu_style: MenuStyle,
+        mtm: MainThreadMarker,
+    ) -> Result<Self> {
         // Create the delegate
         let delegate = MenuBarDelegate::new(event_tx, mtm);
 
@@ -324,12 +1361,14 @@ impl MenuBar {
         // Create the menu
         let menu = NSMenu::new(mtm);
 
-        // Set the icon to a musical note symbol
+        // Set the icon to the configured SF Symbol, or a text note if unavailable
         if let Some(button) = unsafe { status_item.button(mtm) } {
-            // Use a simple text-based icon for now
-            // SF Symbols require newer objc2-app-kit APIs
+            match status_symbol_image(symbol_name) {
+                Some(image) => unsafe { button.setImage(Some(&image)) },
+                None => unsafe { button.setTitle(ns_string!("♪")) },
+            }
             unsafe {
-                button.setTitle(ns_string!("♪"));
+                button.setAccessibilityLabel(Some(ns_string!("Lyricsify")));
             }
         }
 
@@ -343,10 +1382,107 @@ impl MenuBar {
                 ns_string!(""),
             );
             item.setTarget(Some(&delegate));
+            item.setToolTip(Some(ns_string!("Show or hide the lyrics overlay")));
+            item
+        };
+
+        // 2. Next track in the Spotify queue (hidden unless enabled and known)
+        let up_next_item = unsafe {
+            let item = NSMenuItem::initWithTitle_action_keyEquivalent(
+                mtm.alloc::<NSMenuItem>(),
+                ns_string!("Up Next"),
+                None,
+                ns_string!(""),
+            );
+            item.setHidden(true);
+            item
+        };
+
+        // 3. Now playing notifications toggle (checkmark shows state)
+        let notifications_item = unsafe {
+            let item = NSMenuItem::initWithTitle_action_keyEquivalent(
+                mtm.alloc::<NSMenuItem>(),
+                ns_string!("Now Playing Notifications"),
+                Some(objc2::sel!(toggleNotifications:)),
+                ns_string!(""),
+            );
+            item.setTarget(Some(&delegate));
+            item.setToolTip(Some(ns_string!("Show a notification when the track changes")));
+            item
+        };
+
+        // 4. Skip the current track's lyrics from now on (hidden until a track plays)
+        let skip_item = unsafe {
+            let item = NSMenuItem::initWithTitle_action_keyEquivalent(
+                mtm.alloc::<NSMenuItem>(),
+                ns_string!("Never Fetch Lyrics for This Track"),
+                Some(objc2::sel!(skipTrack:)),
+                ns_string!(""),
+            );
+            item.setTarget(Some(&delegate));
+            item.setToolTip(Some(ns_string!(
+                "Stop looking up lyrics for this track, e.g. for instrumentals"
+            )));
+            item.setHidden(true);
+            item
+        };
+
+        // 5. Clear the skip list (hidden while it's empty)
+        let clear_skipped_item = unsafe {
+            let item = NSMenuItem::initWithTitle_action_keyEquivalent(
+                mtm.alloc::<NSMenuItem>(),
+                ns_string!("Clear Skipped Tracks"),
+                Some(objc2::sel!(clearSkippedTracks:)),
+                ns_string!(""),
+            );
+            item.setTarget(Some(&delegate));
+            item.setToolTip(Some(ns_string!("Fetch lyrics again for all skipped tracks")));
+            item.setHidden(true);
+            item
+        };
+
+        // 6. Fetch lyrics on request (only shown in on-demand mode, until fetched)
+        let fetch_now_item = unsafe {
+            let item = NSMenuItem::initWithTitle_action_keyEquivalent(
+                mtm.alloc::<NSMenuItem>(),
+                ns_string!("Fetch Lyrics Now"),
+                Some(objc2::sel!(fetchLyricsNow:)),
+                ns_string!(""),
+            );
+            item.setTarget(Some(&delegate));
+            item.setToolTip(Some(ns_string!("Look up lyrics for the track that's playing")));
+            item.setHidden(true);
+            item
+        };
+
+        // 7. Look up lyrics for a song that isn't playing
+        let lookup_item = unsafe {
+            let item = NSMenuItem::initWithTitle_action_keyEquivalent(
+                mtm.alloc::<NSMenuItem>(),
+                ns_string!("Look Up Lyrics…"),
+                Some(objc2::sel!(lookUpLyrics:)),
+                ns_string!(""),
+            );
+            item.setTarget(Some(&delegate));
+            item.setToolTip(Some(ns_string!("Show lyrics for any song by artist and title")));
+            item
+        };
+
+        // 8. Leave a manual lookup (hidden unless one is showing)
+        let end_lookup_item = unsafe {
+            let item = NSMenuItem::initWithTitle_action_keyEquivalent(
+                mtm.alloc::<NSMenuItem>(),
+                ns_string!("Back to Now Playing"),
+                Some(objc2::sel!(endLookup:)),
+                ns_string!(""),
+            );
+            item.setTarget(Some(&delegate));
+            item.setToolTip(Some(ns_string!("Show lyrics for the song playing on Spotify again")));
+            item.setHidden(true);
             item
         };
 
-        // 2. Authenticate Spotify menu item
+        // 9. Authenticate Spotify menu item
         let auth_item = unsafe {
             let item = NSMenuItem::initWithTitle_action_keyEquivalent(
                 mtm.alloc::<NSMenuItem>(),
@@ -355,10 +1491,125 @@ impl MenuBar {
                 ns_string!(""),
             );
             item.setTarget(Some(&delegate));
+            item.setToolTip(Some(ns_string!("Sign in to Spotify in your browser")));
+            item
+        };
+
+        // 10. Force a token refresh (only shown while authenticated)
+        let refresh_item = unsafe {
+            let item = NSMenuItem::initWithTitle_action_keyEquivalent(
+                mtm.alloc::<NSMenuItem>(),
+                ns_string!("Refresh Spotify Token"),
+                Some(objc2::sel!(refreshToken:)),
+                ns_string!(""),
+            );
+            item.setTarget(Some(&delegate));
+            item.setToolTip(Some(ns_string!("Refresh the Spotify access token now")));
+            item.setHidden(true);
+            item
+        };
+
+        // 11. Restart the playback poller, e.g. after it stopped on repeated crashes
+        let restart_polling_item = unsafe {
+            let item = NSMenuItem::initWithTitle_action_keyEquivalent(
+                mtm.alloc::<NSMenuItem>(),
+                ns_string!("Restart Spotify Connection"),
+                Some(objc2::sel!(restartPolling:)),
+                ns_string!(""),
+            );
+            item.setTarget(Some(&delegate));
+            item.setToolTip(Some(ns_string!("Restart checking Spotify for the playing track")));
+            item.setHidden(true);
+            item
+        };
+
+        // 12. Spotify Connect devices to play on (hidden until devices are listed)
+        let devices_menu = NSMenu::new(mtm);
+        let devices_item = unsafe {
+            let item = NSMenuItem::initWithTitle_action_keyEquivalent(
+                mtm.alloc::<NSMenuItem>(),
+                ns_string!("Play On"),
+                None,
+                ns_string!(""),
+            );
+            item.setToolTip(Some(ns_string!("Choose the Spotify device to play on")));
+            item.setSubmenu(Some(&devices_menu));
+            item.setHidden(true);
+            item
+        };
+
+        // 13. Check the setup (credentials, keychain, Spotify, lyrics providers)
+        let diagnostics_item = unsafe {
+            let item = NSMenuItem::initWithTitle_action_keyEquivalent(
+                mtm.alloc::<NSMenuItem>(),
+                ns_string!("Run Diagnostics"),
+                Some(objc2::sel!(runDiagnostics:)),
+                ns_string!(""),
+            );
+            item.setTarget(Some(&delegate));
+            item.setToolTip(Some(ns_string!(
+                "Check credentials, keychain, Spotify and lyrics providers"
+            )));
+            item
+        };
+
+        // 14. Copy a redacted bug-report bundle to the clipboard
+        let copy_diagnostics_item = unsafe {
+            let item = NSMenuItem::initWithTitle_action_keyEquivalent(
+                mtm.alloc::<NSMenuItem>(),
+                ns_string!("Copy Diagnostics"),
+                Some(objc2::sel!(copyDiagnostics:)),
+                ns_string!(""),
+            );
+            item.setTarget(Some(&delegate));
+            item.setToolTip(Some(ns_string!(
+                "Copy checks, settings, stats and recent logs (secrets removed) for a bug report"
+            )));
+            item
+        };
+
+        // 15. Step the log level for troubleshooting without a restart
+        let log_level_item = unsafe {
+            let item = NSMenuItem::initWithTitle_action_keyEquivalent(
+                mtm.alloc::<NSMenuItem>(),
+                ns_string!("Cycle Log Level"),
+                Some(objc2::sel!(cycleLogLevel:)),
+                ns_string!(""),
+            );
+            item.setTarget(Some(&delegate));
+            item.setToolTip(Some(ns_string!("Switch logging between Info, Debug and Trace")));
+            item
+        };
+
+        // 16. Save the settings to a file, for another install
+        let export_settings_item = unsafe {
+            let item = NSMenuItem::initWithTitle_action_keyEquivalent(
+                mtm.alloc::<NSMenuItem>(),
+                ns_string!("Export Settings…"),
+                Some(objc2::sel!(exportSettings:)),
+                ns_string!(""),
+            );
+            item.setTarget(Some(&delegate));
+            item.setToolTip(Some(ns_string!(
+                "Save settings and the skip list to a file (credential commands are left out)"
+            )));
+            item
+        };
+
+        // 17. Merge settings from an exported file
+        let import_settings_item = unsafe {
+            let item = NSMenuItem::initWithTitle_action_keyEquivalent(
+                mtm.alloc::<NSMenuItem>(),
+                ns_string!("Import Settings…"),
+                Some(objc2::sel!(importSettings:)),
+                ns_string!(""),
+            );
+            item.setTarget(Some(&delegate));
+            item.setToolTip(Some(ns_string!("Load settings exported from another install")));
             item
         };
 
-        // 3. Quit menu item
+        // 18. Quit menu item
         let quit_item = unsafe {
             let item = NSMenuItem::initWithTitle_action_keyEquivalent(
                 mtm.alloc::<NSMenuItem>(),
@@ -367,12 +1618,58 @@ impl MenuBar {
                 ns_string!("q"),
             );
             item.setTarget(Some(&delegate));
+            item.setToolTip(Some(ns_string!("Quit Lyricsify")));
             item
         };
 
-        // Add items to menu
+        // Add items to menu. Items whose visibility changes (fetch, skip,
+        // lookup, auth) stay at the top level in both styles so they're noticed.
         menu.addItem(&toggle_item);
+        menu.addItem(&up_next_item);
+        menu.addItem(&fetch_now_item);
+        menu.addItem(&skip_item);
+        menu.addItem(&clear_skipped_item);
+        menu.addItem(&lookup_item);
+        menu.addItem(&end_lookup_item);
         menu.addItem(&auth_item);
+        menu.addItem(&devices_item);
+
+        let advanced_items = [
+            &notifications_item,
+            &refresh_item,
+            &restart_polling_item,
+            &diagnostics_item,
+            &copy_diagnostics_item,
+            &log_level_item,
+            &export_settings_item,
+            &import_settings_item,
+        ];
+        match menu_style {
+            MenuStyle::Full => {
+                menu.addItem(&NSMenuItem::separatorItem(mtm));
+                for item in advanced_items {
+                    menu.addItem(item);
+                }
+            }
+            MenuStyle::Compact => {
+                let advanced_menu = NSMenu::new(mtm);
+                for item in advanced_items {
+                    advanced_menu.addItem(item);
+                }
+                let advanced_item = unsafe {
+                    let item = NSMenuItem::initWithTitle_action_keyEquivalent(
+                        mtm.alloc::<NSMenuItem>(),
+                        ns_string!("Advanced"),
+                        None,
+                        ns_string!(""),
+                    );
+                    item.setSubmenu(Some(&advanced_menu));
+                    item
+                };
+                menu.addItem(&advanced_item);
+            }
+        }
+
         menu.addItem(
             &NSMenuItem::separatorItem(mtm), // Add separator before quit
         );
@@ -383,12 +1680,47 @@ impl MenuBar {
             status_item.setMenu(Some(&menu));
         }
 
+        // Hear about the Mac waking from sleep, when playback may have moved on
+        unsafe {
+            NSWorkspace::sharedWorkspace()
+                .notificationCenter()
+                .addObserver_selector_name_object(
+                    &delegate,
+                    objc2::sel!(systemDidWake:),
+                    Some(NSWorkspaceDidWakeNotification),
+                    None,
+                );
+        }
+
+        // Hear about other apps coming to the front, for dimming the overlay
+        unsafe {
+            NSWorkspace::sharedWorkspace()
+                .notificationCenter()
+                .addObserver_selector_name_object(
+                    &delegate,
+                    objc2::sel!(appDidActivate:),
+                    Some(NSWorkspaceDidActivateApplicationNotification),
+                    None,
+                );
+        }
+
         Ok(Self {
             status_item,
             menu,
             toggle_item,
+            up_next_item,
+            notifications_item,
+            skip_item,
+            clear_skipped_item,
+            fetch_now_item,
+            end_lookup_item,
             auth_item,
+            refresh_item,
+            restart_polling_item,
+            devices_item,
+            devices_menu,
             delegate,
+            mtm,
             overlay_visible: Arc::new(Mutex::new(false)),
             authenticated: Arc::new(Mutex::new(false)),
         })
@@ -417,15 +1749,149 @@ impl MenuBar {
         Ok(())
     }
 
+    /// Update the checkmark on the now playing notifications item
+    pub fn update_notifications_state(&self, enabled: bool) -> Result<()> {
+        let state = if enabled {
+            NSControlStateValueOn
+        } else {
+            NSControlStateValueOff
+        };
+        unsafe {
+            self.notifications_item.setState(state);
+        }
+        Ok(())
+    }
+
+    /// Update the skip menu items for the current track and skip list
+    ///
+    /// The skip action is only offered while a track that isn't already
+    /// skipped is playing, and clearing only while the list is non-empty.
+    pub fn update_skip_state(&self, can_skip_current: bool, skipped_count: usize) -> Result<()> {
+        unsafe {
+            self.skip_item.setHidden(!can_skip_current);
+            self.clear_skipped_item.setHidden(skipped_count == 0);
+        }
+        Ok(())
+    }
+
+    /// Screen frame of the status item's icon and the visible frame of its
+    /// screen, `None` while the icon isn't on screen
+    ///
+    /// Icons pushed out of the menu bar (by the notch or too many items)
+    /// still have a window, but it isn't on any screen.
+    pub fn icon_frame(&self) -> Option<(CGRect, CGRect)> {
+        unsafe {
+            let window = self.status_item.button(self.mtm)?.window()?;
+            let screen = window.screen()?;
+            Some((window.frame(), screen.visibleFrame()))
+        }
+    }
+
+    /// Update the now-playing description announced for the status item
+    pub fn update_now_playing(&self, now_playing: Option<&str>) -> Result<()> {
+        if let Some(button) = unsafe { self.status_item.button(self.mtm) } {
+            let description = NSString::from_str(now_playing.unwrap_or("Nothing playing"));
+            let value: &AnyObject = &description;
+            unsafe {
+                button.setAccessibilityValue(Some(value));
+            }
+        }
+        Ok(())
+    }
+
+    /// Show the next queued track as an informational item, hidden when `None`
+    pub fn update_up_next(&self, up_next: Option<&str>) -> Result<()> {
+        unsafe {
+            if let Some(up_next) = up_next {
+                self.up_next_item.setTitle(&NSString::from_str(&format!("Up Next: {}", up_next)));
+            }
+            self.up_next_item.setHidden(up_next.is_none());
+        }
+        Ok(())
+    }
+
+    /// Offer "Fetch Lyrics Now" while the playing track's lyrics wait on a request
+    pub fn update_fetch_now_state(&self, available: bool) -> Result<()> {
+        unsafe {
+            self.fetch_now_item.setHidden(!available);
+        }
+        Ok(())
+    }
+
+    /// Show "Back to Now Playing" while a manual lookup is displayed
+    pub fn update_lookup_state(&self, active: bool) -> Result<()> {
+        unsafe {
+            self.end_lookup_item.setHidden(!active);
+        }
+        Ok(())
+    }
+
+    /// Rebuild the device submenu, checking the active device
+    ///
+    /// Each item's tag is its index in `devices`, which is what a selection
+    /// reports back. With no devices the submenu says so and stays visible, so
+    /// "Refresh Devices" can still be reached. Without `can_switch` (free
+    /// accounts) devices are listed but can't be picked.
+    pub fn update_devices(&self, devices: &[DeviceInfo], can_switch: bool) -> Result<()> {
+        unsafe {
+            self.devices_menu.removeAllItems();
+            for (index, device) in devices.iter().enumerate() {
+                // Items without an action are shown disabled
+                let item = NSMenuItem::initWithTitle_action_keyEquivalent(
+                    self.mtm.alloc::<NSMenuItem>(),
+                    &NSString::from_str(&device.name),
+                    can_switch.then_some(objc2::sel!(selectDevice:)),
+                    ns_string!(""),
+                );
+                item.setTarget(Some(&self.delegate));
+                item.setTag(index as isize);
+                if device.is_active {
+                    item.setState(NSControlStateValueOn);
+                }
+                self.devices_menu.addItem(&item);
+            }
+            if devices.is_empty() {
+                let none = NSMenuItem::initWithTitle_action_keyEquivalent(
+                    self.mtm.alloc::<NSMenuItem>(),
+                    ns_string!("No devices found"),
+                    None,
+                    ns_string!(""),
+                );
+                none.setEnabled(false);
+                self.devices_menu.addItem(&none);
+            }
+
+            self.devices_menu.addItem(&NSMenuItem::separatorItem(self.mtm));
+            let refresh = NSMenuItem::initWithTitle_action_keyEquivalent(
+                self.mtm.alloc::<NSMenuItem>(),
+                ns_string!("Refresh Devices"),
+                Some(objc2::sel!(refreshDevices:)),
+                ns_string!(""),
+            );
+            refresh.setTarget(Some(&self.delegate));
+            self.devices_menu.addItem(&refresh);
+
+            self.devices_item.setHidden(false);
+            self.devices_item.setToolTip(Some(if can_switch {
+                ns_string!("Choose the Spotify device to play on")
+            } else {
+                ns_string!("Switching devices requires Spotify Premium")
+            }));
+        }
+        Ok(())
+    }
+
     /// Update the authentication state
     pub fn update_auth_state(&self, authenticated: bool) -> Result<()> {
         if let Ok(mut auth) = self.authenticated.lock() {
             *auth = authenticated;
         }
 
-        // Show/hide the authenticate menu item based on auth state
+        // Show/hide the authenticate, refresh and restart menu items based on auth state
         unsafe {
             self.auth_item.setHidden(authenticated);
+            self.refresh_item.setHidden(!authenticated);
+            self.restart_polling_item.setHidden(!authenticated);
         }
 
         Ok(())
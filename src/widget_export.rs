@@ -0,0 +1,110 @@
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::error::{LyricsifyError, Result};
+use crate::spotify_client::{PlaybackProgress, TrackInfo};
+
+/// File written inside the app group container
+const FILE_NAME: &str = "now_playing.json";
+
+/// Snapshot of what is playing, shared with external widgets
+///
+/// This is the file format companion widgets read, so fields may be added
+/// but existing ones must not be renamed or removed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WidgetState {
+    pub track_id: Option<String>,
+    pub title: Option<String>,
+    pub artists: Vec<String>,
+    pub progress_ms: u64,
+    pub duration_ms: u64,
+    pub is_playing: bool,
+    /// The lyric line being sung; `None` until synced lyrics are available
+    pub active_line: Option<String>,
+    /// Unix timestamp (ms) of this snapshot, so readers can extrapolate progress
+    pub updated_at_ms: i64,
+}
+
+impl WidgetState {
+    /// State for a track at the given playback position
+    pub fn playing(track: &TrackInfo, progress: &PlaybackProgress) -> Self {
+        Self {
+            track_id: Some(track.id.clone()),
+            title: Some(track.name.clone()),
+            artists: track.artists.clone(),
+            progress_ms: progress.progress_ms,
+            duration_ms: progress.duration_ms,
+            is_playing: progress.is_playing,
+            active_line: None,
+            updated_at_ms: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    /// State when nothing is playing
+    pub fn stopped() -> Self {
+        Self {
+            track_id: None,
+            title: None,
+            artists: Vec::new(),
+            progress_ms: 0,
+            duration_ms: 0,
+            is_playing: false,
+            active_line: None,
+            updated_at_ms: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    /// Get the path of the shared file for an app group
+    fn file_path(group_id: &str) -> Result<PathBuf> {
+        let home = std::env::var("HOME").map_err(|_| {
+            LyricsifyError::ConfigError("HOME environment variable not set".to_string())
+        })?;
+
+        Ok(PathBuf::from(home)
+            .join("Library")
+            .join("Group Containers")
+            .join(group_id)
+            .join(FILE_NAME))
+    }
+
+    /// Write the state to the app group container
+    ///
+    /// Written to a temporary file and renamed into place, so a widget never
+    /// reads a half-written file.
+    pub fn save(&self, group_id: &str) -> Result<()> {
+        let path = Self::file_path(group_id)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| {
+                LyricsifyError::ConfigError(format!("Failed to create group container: {}", e))
+            })?;
+        }
+
+        let json = serde_json::to_string(self)?;
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, json)?;
+        fs::rename(&temp_path, &path)?;
+
+        log::debug!("Wrote widget state to {:?}", path);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_widget_json_field_names() {
+        let state = WidgetState {
+            updated_at_ms: 1_700_000_000_000,
+            ..WidgetState::stopped()
+        };
+
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(
+            json,
+            r#"{"track_id":null,"title":null,"artists":[],"progress_ms":0,"duration_ms":0,"is_playing":false,"active_line":null,"updated_at_ms":1700000000000}"#
+        );
+    }
+}